@@ -0,0 +1,88 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use respirator::{parse_all, resp, resp_ref};
+
+fn bulk_string(len: usize) -> Vec<u8> {
+    let mut input = format!("${}\r\n", len).into_bytes();
+    input.resize(input.len() + len, b'x');
+    input.extend_from_slice(b"\r\n");
+    input
+}
+
+fn nested_array(depth: usize) -> Vec<u8> {
+    let mut input = b"*1\r\n".repeat(depth);
+    input.extend_from_slice(b":1\r\n");
+    input
+}
+
+/// Mix of requests and replies a client pipelining commands would see.
+fn pipeline(commands: usize) -> Vec<u8> {
+    let mut input = Vec::new();
+    for i in 0..commands {
+        let key = format!("key:{}", i);
+        input.extend_from_slice(
+            format!(
+                "*3\r\n$3\r\nSET\r\n${}\r\n{}\r\n$5\r\nvalue\r\n",
+                key.len(),
+                key
+            )
+            .as_bytes(),
+        );
+        input.extend_from_slice(b"+OK\r\n");
+        input.extend_from_slice(format!(":{}\r\n", i).as_bytes());
+        input.extend_from_slice(b"$-1\r\n");
+    }
+    input
+}
+
+fn simple_strings(c: &mut Criterion) {
+    let input = &b"+OK\r\n"[..];
+    let mut group = c.benchmark_group("simple_string");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("resp", |b| b.iter(|| resp(black_box(input))));
+    group.bench_function("resp_ref", |b| b.iter(|| resp_ref(black_box(input))));
+    group.finish();
+}
+
+fn bulk_strings(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bulk_string");
+    for len in [1024, 1024 * 1024] {
+        let input = bulk_string(len);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("resp", len), &input, |b, input| {
+            b.iter(|| resp(black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("resp_ref", len), &input, |b, input| {
+            b.iter(|| resp_ref(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+fn nested_arrays(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nested_array");
+    for depth in [8, 64] {
+        let input = nested_array(depth);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &input, |b, input| {
+            b.iter(|| resp(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+fn mixed_pipeline(c: &mut Criterion) {
+    let input = pipeline(1000);
+    let mut group = c.benchmark_group("pipeline");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("parse_all", |b| b.iter(|| parse_all(black_box(&input))));
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    simple_strings,
+    bulk_strings,
+    nested_arrays,
+    mixed_pipeline
+);
+criterion_main!(benches);
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use respirator::{resp, resp_ref, resp_streaming};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((rest, value)) = resp(data) {
+        assert!(rest.len() < data.len());
+        assert_eq!(value.encoded_len(), value.encode().len());
+        let (_, borrowed) = resp_ref(data).expect("resp_ref rejected input accepted by resp");
+        assert_eq!(borrowed.to_owned().encode(), value.encode());
+    }
+    let _ = resp_streaming(data);
+});
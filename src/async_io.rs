@@ -0,0 +1,238 @@
+use std::io;
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{
+    stream::{invalid_data, unexpected_eof, Framer, READ_CHUNK_SIZE},
+    ParseConfig, Parser, Resp,
+};
+
+/// Asynchronous counterpart of [`RespReader`](crate::RespReader), reading RESP values from any `tokio::io::AsyncRead`.
+///
+/// # Examples
+/// ```
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// use respirator::{AsyncRespReader, Resp};
+///
+/// let mut reader = AsyncRespReader::new(&b"+OK\r\n:8\r\n"[..]);
+/// assert_eq!(reader.read_value().await.unwrap(), Resp::SimpleString(b"OK".to_vec()));
+/// assert_eq!(reader.read_value().await.unwrap(), Resp::Integer(8));
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncRespReader<R> {
+    reader: R,
+    parser: Parser,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRespReader<R> {
+    /// Creates adapter reading from given reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - an asynchronous reader to read RESP values from
+    pub fn new(reader: R) -> Self {
+        Self::with_config(reader, ParseConfig::default())
+    }
+
+    /// Creates adapter reading from given reader, parsing according to the given [`ParseConfig`].
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - an asynchronous reader to read RESP values from
+    /// * `config` - options of the parser
+    pub fn with_config(reader: R, config: ParseConfig) -> Self {
+        AsyncRespReader {
+            reader,
+            parser: Parser::with_config(config),
+        }
+    }
+
+    /// Reads next value, awaiting more bytes from the underlying reader until a complete value is buffered.
+    /// Fails with `UnexpectedEof` if the reader ends before a complete value
+    /// and with `InvalidData` if read bytes are not valid RESP.
+    pub async fn read_value(&mut self) -> io::Result<Resp> {
+        let mut chunk = [0; READ_CHUNK_SIZE];
+        loop {
+            match self.parser.try_next() {
+                Ok(Some(value)) => return Ok(value),
+                Ok(None) => {}
+                Err(kind) => return Err(invalid_data(kind)),
+            }
+            let read = self.reader.read(&mut chunk).await?;
+            if read == 0 {
+                return Err(unexpected_eof());
+            }
+            self.parser.feed(&chunk[..read]);
+        }
+    }
+
+    /// Returns the underlying reader, discarding buffered bytes.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+/// `tokio_util` codec framing a byte stream into RESP values and serializing them back,
+/// so that e.g. `Framed::new(socket, RespCodec::default())` is a stream and sink of `Resp`.
+///
+/// # Examples
+/// ```
+/// use bytes::BytesMut;
+/// use respirator::{Resp, RespCodec};
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// let mut codec = RespCodec::default();
+/// let mut buf = BytesMut::new();
+/// codec.encode(Resp::Integer(8), &mut buf).unwrap();
+/// assert_eq!(&buf[..], b":8\r\n");
+/// assert_eq!(codec.decode(&mut buf).unwrap(), Some(Resp::Integer(8)));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct RespCodec {
+    config: ParseConfig,
+    framer: Framer,
+}
+
+impl Default for RespCodec {
+    fn default() -> Self {
+        Self::with_config(ParseConfig::default())
+    }
+}
+
+impl RespCodec {
+    /// Creates codec decoding according to the given [`ParseConfig`], e.g. to bound nesting of values sent by a peer.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - options of the parser
+    ///
+    /// # Examples
+    /// ```
+    /// use bytes::BytesMut;
+    /// use respirator::{ParseConfig, RespCodec};
+    /// use tokio_util::codec::Decoder;
+    ///
+    /// let mut codec = RespCodec::with_config(ParseConfig::new().max_array_len(1));
+    /// let mut buf = BytesMut::from(&b"*2\r\n"[..]);
+    /// assert!(codec.decode(&mut buf).is_err());
+    /// ```
+    pub fn with_config(config: ParseConfig) -> Self {
+        RespCodec {
+            config,
+            framer: Framer::default(),
+        }
+    }
+}
+
+/// Decodes complete values only, leaving partial frames in the buffer until more bytes arrive.
+impl Decoder for RespCodec {
+    type Item = Resp;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<Self::Item>> {
+        match self.framer.next(src, &self.config) {
+            Ok(Some((value, len))) => {
+                src.advance(len);
+                Ok(Some(value))
+            }
+            Ok(None) => Ok(None),
+            Err(error) => Err(invalid_data(error)),
+        }
+    }
+}
+
+impl Encoder<Resp> for RespCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Resp, dst: &mut BytesMut) -> io::Result<()> {
+        item.encode_to(&mut dst.writer())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reads_bulk_string_split_across_reads() {
+        let chunked = AsyncReadExt::chain(
+            AsyncReadExt::chain(&b"$11\r\nhel"[..], &b"lo wor"[..]),
+            &b"ld\r\n:1"[..],
+        );
+        let mut reader = AsyncRespReader::new(chunked);
+        assert_eq!(
+            reader.read_value().await.unwrap(),
+            Resp::BulkString(Some(b"hello world".to_vec()))
+        );
+        assert_eq!(
+            reader.read_value().await.unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_reading_malformed_stream() {
+        let mut reader = AsyncRespReader::new(&b"$4\r\nbad\r\n"[..]);
+        assert_eq!(
+            reader.read_value().await.unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn decodes_partial_frames() {
+        let mut codec = RespCodec::default();
+        let mut buf = BytesMut::from(&b"$11\r\nhello"[..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        assert_eq!(&buf[..], b"$11\r\nhello");
+
+        buf.extend_from_slice(b" world\r\n+OK");
+        assert_eq!(
+            codec.decode(&mut buf).unwrap(),
+            Some(Resp::BulkString(Some(b"hello world".to_vec())))
+        );
+        assert_eq!(&buf[..], b"+OK");
+    }
+
+    #[test]
+    fn fails_decoding_malformed_frame() {
+        let mut codec = RespCodec::default();
+        let mut buf = BytesMut::from(&b"?\r\n"[..]);
+        assert_eq!(
+            codec.decode(&mut buf).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_according_to_config() {
+        let config = ParseConfig::new().max_depth(1);
+        let mut reader = AsyncRespReader::with_config(&b"*1\r\n*1\r\n:1\r\n"[..], config);
+        assert_eq!(
+            reader.read_value().await.unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn decodes_according_to_config() {
+        let mut codec = RespCodec::with_config(ParseConfig::new().lenient_line_endings(true));
+        let mut buf = BytesMut::from(&b":8\n"[..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(Resp::Integer(8)));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn encodes_frames() {
+        let mut codec = RespCodec::default();
+        let mut buf = BytesMut::new();
+        let value = Resp::Array(Some(vec!["GET".into(), "key".into()]));
+        codec.encode(value.clone(), &mut buf).unwrap();
+        assert_eq!(&buf[..], &value.encode()[..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(value));
+        assert!(buf.is_empty());
+    }
+}
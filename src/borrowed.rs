@@ -0,0 +1,234 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{parser::FromWire, Resp};
+
+/// Zero-copy counterpart of [`Resp`], returned by [`resp_ref`](crate::resp_ref).
+/// Its string-like variants borrow from the parsed input instead of allocating their own `Vec<u8>`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RespRef<'a> {
+    /// Simple string in RESP.
+    SimpleString(&'a [u8]),
+    /// Integer in RESP.
+    Integer(i64),
+    /// Error in RESP.
+    Error(&'a [u8]),
+    /// Bulk String in RESP, contains None if encounters null bulk string (`$-1`).
+    BulkString(Option<&'a [u8]>),
+    /// Streamed Bulk String in RESP3 (`$?`), holding its chunks in order, as they are not contiguous in the input.
+    StreamedString(Vec<&'a [u8]>),
+    /// Array in RESP, contains None if encounters null array (`*-1`).
+    Array(Option<Vec<RespRef<'a>>>),
+    /// Double in RESP3.
+    Double(f64),
+    /// Boolean in RESP3.
+    Boolean(bool),
+    /// Null in RESP3.
+    Null,
+    /// Map in RESP3.
+    Map(Vec<(RespRef<'a>, RespRef<'a>)>),
+    /// Set in RESP3.
+    Set(Vec<RespRef<'a>>),
+    /// Push in RESP3.
+    Push(Vec<RespRef<'a>>),
+    /// Big Number in RESP3.
+    BigNumber(&'a [u8]),
+    /// Verbatim String in RESP3.
+    VerbatimString { format: [u8; 3], data: &'a [u8] },
+    /// Bulk Error in RESP3.
+    BulkError(&'a [u8]),
+    /// Attribute in RESP3.
+    Attribute {
+        attributes: Vec<(RespRef<'a>, RespRef<'a>)>,
+        value: Box<RespRef<'a>>,
+    },
+}
+
+impl RespRef<'_> {
+    /// Copies borrowed payloads, converting value into the owned [`Resp`].
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::{resp_ref, Resp};
+    ///
+    /// let (_, parsed) = resp_ref(&b"*1\r\n+OK\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.to_owned(), Resp::Array(Some(vec![Resp::SimpleString(b"OK".to_vec())])));
+    /// ```
+    pub fn to_owned(&self) -> Resp {
+        self.to_owned_with(&mut <[u8]>::to_vec)
+    }
+
+    /// Converts value into the owned [`Resp`] like [`to_owned`](RespRef::to_owned),
+    /// but lets the callback provide the storage of every non-null Bulk String payload,
+    /// e.g. to take a buffer out of a pool or to reuse one for payloads seen before.
+    ///
+    /// # Arguments
+    ///
+    /// * `bulk` - callback turning borrowed Bulk String payload into an owned one
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::{resp_ref, Resp};
+    ///
+    /// let (_, parsed) = resp_ref(&b"*2\r\n$3\r\nkey\r\n+OK\r\n"[..]).unwrap();
+    /// let mut seen = Vec::new();
+    /// let owned = parsed.to_owned_with(&mut |val: &[u8]| {
+    ///     seen.push(val.to_vec());
+    ///     val.to_ascii_uppercase()
+    /// });
+    /// assert_eq!(seen, vec![b"key".to_vec()]);
+    /// assert_eq!(owned.as_array().unwrap()[0], Resp::BulkString(Some(b"KEY".to_vec())));
+    /// ```
+    pub fn to_owned_with<F: FnMut(&[u8]) -> Vec<u8>>(&self, bulk: &mut F) -> Resp {
+        match self {
+            RespRef::SimpleString(val) => Resp::SimpleString(val.to_vec()),
+            RespRef::Integer(val) => Resp::Integer(*val),
+            RespRef::Error(val) => Resp::Error(val.to_vec()),
+            RespRef::BulkString(val) => Resp::BulkString(val.map(&mut *bulk)),
+            RespRef::StreamedString(chunks) => Resp::BulkString(Some(bulk(&chunks.concat()))),
+            RespRef::Array(values) => {
+                Resp::Array(values.as_deref().map(|values| to_owned_all(values, bulk)))
+            }
+            RespRef::Double(val) => Resp::Double(*val),
+            RespRef::Boolean(val) => Resp::Boolean(*val),
+            RespRef::Null => Resp::Null,
+            RespRef::Map(entries) => Resp::Map(to_owned_entries(entries, bulk)),
+            RespRef::Set(values) => Resp::Set(to_owned_all(values, bulk)),
+            RespRef::Push(values) => Resp::Push(to_owned_all(values, bulk)),
+            RespRef::BigNumber(val) => Resp::BigNumber(val.to_vec()),
+            RespRef::VerbatimString { format, data } => Resp::VerbatimString {
+                format: *format,
+                data: data.to_vec(),
+            },
+            RespRef::BulkError(val) => Resp::BulkError(val.to_vec()),
+            RespRef::Attribute { attributes, value } => Resp::Attribute {
+                attributes: to_owned_entries(attributes, bulk),
+                value: Box::new(value.to_owned_with(bulk)),
+            },
+        }
+    }
+}
+
+fn to_owned_all<F: FnMut(&[u8]) -> Vec<u8>>(values: &[RespRef<'_>], bulk: &mut F) -> Vec<Resp> {
+    values
+        .iter()
+        .map(|value| value.to_owned_with(bulk))
+        .collect()
+}
+
+fn to_owned_entries<F: FnMut(&[u8]) -> Vec<u8>>(
+    entries: &[(RespRef<'_>, RespRef<'_>)],
+    bulk: &mut F,
+) -> Vec<(Resp, Resp)> {
+    entries
+        .iter()
+        .map(|(key, value)| (key.to_owned_with(bulk), value.to_owned_with(bulk)))
+        .collect()
+}
+
+impl<'a> FromWire<'a> for RespRef<'a> {
+    fn simple_string(val: &'a [u8]) -> Self {
+        RespRef::SimpleString(val)
+    }
+
+    fn integer(val: i64) -> Self {
+        RespRef::Integer(val)
+    }
+
+    fn error(val: &'a [u8]) -> Self {
+        RespRef::Error(val)
+    }
+
+    fn bulk_string(val: Option<&'a [u8]>) -> Self {
+        RespRef::BulkString(val)
+    }
+
+    fn streamed_string(chunks: Vec<&'a [u8]>) -> Self {
+        RespRef::StreamedString(chunks)
+    }
+
+    fn array(values: Option<Vec<Self>>) -> Self {
+        RespRef::Array(values)
+    }
+
+    fn double(val: f64) -> Self {
+        RespRef::Double(val)
+    }
+
+    fn boolean(val: bool) -> Self {
+        RespRef::Boolean(val)
+    }
+
+    fn null() -> Self {
+        RespRef::Null
+    }
+
+    fn map(entries: Vec<(Self, Self)>) -> Self {
+        RespRef::Map(entries)
+    }
+
+    fn set(values: Vec<Self>) -> Self {
+        RespRef::Set(values)
+    }
+
+    fn push(values: Vec<Self>) -> Self {
+        RespRef::Push(values)
+    }
+
+    fn big_number(val: &'a [u8]) -> Self {
+        RespRef::BigNumber(val)
+    }
+
+    fn verbatim_string(format: [u8; 3], data: &'a [u8]) -> Self {
+        RespRef::VerbatimString { format, data }
+    }
+
+    fn bulk_error(val: &'a [u8]) -> Self {
+        RespRef::BulkError(val)
+    }
+
+    fn attribute(attributes: Vec<(Self, Self)>, value: Self) -> Self {
+        RespRef::Attribute {
+            attributes,
+            value: Box::new(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{resp, resp_ref, resp_with_bulk};
+
+    #[test]
+    fn borrows_from_input() {
+        let input = &b"$4\r\ngood\r\n"[..];
+        let (_, parsed) = resp_ref(input).unwrap();
+        if let super::RespRef::BulkString(Some(parsed)) = parsed {
+            assert_eq!(parsed.as_ptr(), input[4..].as_ptr());
+        } else {
+            panic!("Error parsing BulkString");
+        }
+    }
+
+    #[test]
+    fn converts_to_owned() {
+        let input =
+            &b"*4\r\n+OK\r\n%1\r\n$1\r\na\r\n:1\r\n~1\r\n_\r\n=15\r\ntxt:Some string\r\n"[..];
+        let (_, borrowed) = resp_ref(input).unwrap();
+        let (_, owned) = resp(input).unwrap();
+        assert_eq!(borrowed.to_owned(), owned);
+    }
+
+    #[test]
+    fn substitutes_nested_bulk_payloads() {
+        let input = &b"|1\r\n$1\r\na\r\n$-1\r\n%1\r\n$1\r\nb\r\n+c\r\n"[..];
+        let mut calls = 0;
+        let (_, parsed) = resp_with_bulk(input, |val| {
+            calls += 1;
+            val.to_ascii_uppercase()
+        })
+        .unwrap();
+        assert_eq!(calls, 2);
+        let (_, expected) = resp(&b"|1\r\n$1\r\nA\r\n$-1\r\n%1\r\n$1\r\nB\r\n+c\r\n"[..]).unwrap();
+        assert_eq!(parsed, expected);
+    }
+}
@@ -0,0 +1,104 @@
+use alloc::vec::Vec;
+
+use crate::Resp;
+
+/// Kind of cluster redirection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedirectKind {
+    /// Slot has been permanently moved to another node (`MOVED`).
+    Moved,
+    /// Slot is being migrated, only the next command should be sent to another node (`ASK`).
+    Ask,
+}
+
+/// Cluster redirection, parsed out of `-MOVED <slot> <host>:<port>` or `-ASK <slot> <host>:<port>` error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Redirect {
+    /// Whether redirection is permanent or one-off.
+    pub kind: RedirectKind,
+    /// Hash slot of the key.
+    pub slot: u16,
+    /// Host of the node serving the slot, empty if the client should keep the host it's connected to.
+    pub host: Vec<u8>,
+    /// Port of the node serving the slot.
+    pub port: u16,
+}
+
+impl Resp {
+    /// Parses `MOVED` or `ASK` redirection out of `Error` or `BulkError`, None for other values.
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::{RedirectKind, Resp};
+    ///
+    /// let (_, parsed) = respirator::resp(&b"-MOVED 3999 127.0.0.1:6381\r\n"[..]).unwrap();
+    /// let redirect = parsed.as_redirect().unwrap();
+    /// assert_eq!(redirect.kind, RedirectKind::Moved);
+    /// assert_eq!(redirect.slot, 3999);
+    /// assert_eq!(redirect.host, b"127.0.0.1".to_vec());
+    /// assert_eq!(redirect.port, 6381);
+    /// ```
+    pub fn as_redirect(&self) -> Option<Redirect> {
+        let kind = match self.error_code()? {
+            b"MOVED" => RedirectKind::Moved,
+            b"ASK" => RedirectKind::Ask,
+            _ => return None,
+        };
+        let message = self.error_message()?;
+        let space = message.iter().position(|byte| *byte == b' ')?;
+        let (slot, address) = (&message[..space], &message[space + 1..]);
+        // Host may be an IPv6 address, so port follows the last colon.
+        let colon = address.iter().rposition(|byte| *byte == b':')?;
+        let (host, port) = (&address[..colon], &address[colon + 1..]);
+        Some(Redirect {
+            kind,
+            slot: decimal(slot)?,
+            host: host.to_vec(),
+            port: decimal(port)?,
+        })
+    }
+}
+
+fn decimal(val: &[u8]) -> Option<u16> {
+    if val.is_empty() || !val.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    core::str::from_utf8(val).ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_redirects() {
+        let ask = Resp::BulkError(b"ASK 12182 [::1]:7002".to_vec());
+        assert_eq!(
+            ask.as_redirect(),
+            Some(Redirect {
+                kind: RedirectKind::Ask,
+                slot: 12182,
+                host: b"[::1]".to_vec(),
+                port: 7002,
+            })
+        );
+
+        let same_host = Resp::Error(b"MOVED 1 :6380".to_vec());
+        assert_eq!(same_host.as_redirect().unwrap().host, b"".to_vec());
+    }
+
+    #[test]
+    fn rejects_other_values() {
+        for value in [
+            Resp::Error(b"ERR unknown command".to_vec()),
+            Resp::Error(b"MOVED".to_vec()),
+            Resp::Error(b"MOVED 3999".to_vec()),
+            Resp::Error(b"MOVED 3999 127.0.0.1".to_vec()),
+            Resp::Error(b"MOVED +3999 127.0.0.1:6381".to_vec()),
+            Resp::Error(b"MOVED 3999 127.0.0.1:70000".to_vec()),
+            Resp::SimpleString(b"MOVED 3999 127.0.0.1:6381".to_vec()),
+        ] {
+            assert_eq!(value.as_redirect(), None);
+        }
+    }
+}
@@ -0,0 +1,508 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use nom::{Err, IResult};
+
+use crate::{
+    parser::{line, value, Context},
+    type_byte, ParseConfig, Resp, RespError,
+};
+
+/// Client request, i.e. command name with its arguments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Command {
+    /// Name of the command, e.g. `GET`.
+    pub name: Vec<u8>,
+    /// Arguments following command name.
+    pub args: Vec<Vec<u8>>,
+}
+
+/// Parses client request sent as an Array of Bulk Strings, with the first one being command name.
+/// Fails if parsed value is not a non-empty Array of non-null Bulk Strings.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::parse_command;
+///
+/// let (_, command) = parse_command(&b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n"[..]).unwrap();
+/// assert_eq!(command.name, b"GET".to_vec());
+/// assert_eq!(command.args, vec![b"key".to_vec()]);
+/// ```
+pub fn parse_command(input: &[u8]) -> IResult<&[u8], Command, RespError> {
+    parse_command_with_config(input, &ParseConfig::default())
+}
+
+/// Variant of [`parse_command`] parsing according to the given [`ParseConfig`],
+/// e.g. bounding the number and length of arguments an untrusted client may send.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+/// * `config` - options of the parser
+///
+/// # Examples
+/// ```
+/// use respirator::{parse_command_with_config, ParseConfig, RespError};
+///
+/// let config = ParseConfig::new().max_bulk_len(8);
+/// let parsed = parse_command_with_config(&b"*1\r\n$16\r\n"[..], &config);
+/// assert_eq!(parsed, Err(nom::Err::Error(RespError::LimitExceeded)));
+/// ```
+pub fn parse_command_with_config<'a>(
+    input: &'a [u8],
+    config: &ParseConfig,
+) -> IResult<&'a [u8], Command, RespError> {
+    let (rest, mut args) = multi_bulk(input, Context::new(false, *config))?;
+    let name = args.remove(0);
+    Ok((rest, Command { name, args }))
+}
+
+/// Parses client request in either of the forms clients send it in, returning command name followed by its arguments:
+/// an Array of Bulk Strings (see [`parse_command`]) or, for input not starting with a RESP type byte,
+/// an inline command (see [`inline_command`]).
+/// Fails on any other top-level value, e.g. a Simple String or an Integer, which clients never send.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::{request, RespError};
+///
+/// let (_, args) = request(&b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n"[..]).unwrap();
+/// assert_eq!(args, vec![b"GET".to_vec(), b"key".to_vec()]);
+///
+/// let (_, args) = request(&b"GET key\r\n"[..]).unwrap();
+/// assert_eq!(args, vec![b"GET".to_vec(), b"key".to_vec()]);
+///
+/// assert_eq!(request(&b":1\r\n"[..]), Err(nom::Err::Error(RespError::InvalidCommand)));
+/// ```
+pub fn request(input: &[u8]) -> IResult<&[u8], Vec<Vec<u8>>, RespError> {
+    request_with_config(input, &ParseConfig::default())
+}
+
+/// Variant of [`request`] parsing according to the given [`ParseConfig`].
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+/// * `config` - options of the parser
+///
+/// # Examples
+/// ```
+/// use respirator::{request_with_config, ParseConfig, RespError};
+///
+/// let config = ParseConfig::new().max_array_len(2);
+/// let parsed = request_with_config(&b"*3\r\n"[..], &config);
+/// assert_eq!(parsed, Err(nom::Err::Error(RespError::LimitExceeded)));
+/// ```
+pub fn request_with_config<'a>(
+    input: &'a [u8],
+    config: &ParseConfig,
+) -> IResult<&'a [u8], Vec<Vec<u8>>, RespError> {
+    any_request(input, Context::new(false, *config))
+}
+
+/// Streaming variant of [`request_with_config`], for servers reading requests from a socket:
+/// returns `nom::Err::Incomplete` when more data is needed, leaving `Error` for malformed requests.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+/// * `config` - options of the parser
+///
+/// # Examples
+/// ```
+/// use respirator::{request_streaming_with_config, ParseConfig};
+///
+/// let config = ParseConfig::default();
+/// assert!(matches!(
+///     request_streaming_with_config(&b"*2\r\n$3\r\nGET\r\n"[..], &config),
+///     Err(nom::Err::Incomplete(_))
+/// ));
+/// assert!(matches!(
+///     request_streaming_with_config(&b"PI"[..], &config),
+///     Err(nom::Err::Incomplete(_))
+/// ));
+/// ```
+pub fn request_streaming_with_config<'a>(
+    input: &'a [u8],
+    config: &ParseConfig,
+) -> IResult<&'a [u8], Vec<Vec<u8>>, RespError> {
+    any_request(input, Context::new(true, *config))
+}
+
+fn any_request(input: &[u8], ctx: Context) -> IResult<&[u8], Vec<Vec<u8>>, RespError> {
+    match input.first() {
+        Some(b'*') => multi_bulk(input, ctx),
+        Some(byte) if type_byte(*byte).is_some() => Err(Err::Error(RespError::InvalidCommand)),
+        _ => inline(input, ctx),
+    }
+}
+
+/// Parses non-empty Array of non-null Bulk Strings.
+fn multi_bulk(input: &[u8], ctx: Context) -> IResult<&[u8], Vec<Vec<u8>>, RespError> {
+    let (rest, value) = value::<Resp>(input, ctx)?;
+    let args = match value {
+        Resp::Array(Some(values)) => values
+            .into_iter()
+            .map(|value| match value {
+                Resp::BulkString(Some(arg)) => Some(arg),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>(),
+        _ => None,
+    }
+    .filter(|args| !args.is_empty())
+    .ok_or(Err::Error(RespError::InvalidCommand))?;
+    Ok((rest, args))
+}
+
+/// Parses inline command, i.e. space separated arguments terminated by CRLF, as sent by telnet-like clients.
+/// Runs of spaces between arguments are skipped, a line with no arguments is rejected with `InvalidCommand`.
+/// Like line-based values, the line can't contain a stray CR.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::inline_command;
+///
+/// let (_, args) = inline_command(&b"SET key value\r\n"[..]).unwrap();
+/// assert_eq!(args, vec![b"SET".to_vec(), b"key".to_vec(), b"value".to_vec()]);
+/// ```
+pub fn inline_command(input: &[u8]) -> IResult<&[u8], Vec<Vec<u8>>, RespError> {
+    inline(input, Context::new(false, ParseConfig::default()))
+}
+
+/// Variant of [`inline_command`] parsing according to the given [`ParseConfig`],
+/// e.g. accepting lines terminated with a bare LF, as sent by telnet-like clients, in lenient mode.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+/// * `config` - options of the parser
+///
+/// # Examples
+/// ```
+/// use respirator::{inline_command_with_config, ParseConfig};
+///
+/// let config = ParseConfig::new().lenient_line_endings(true);
+/// let (_, args) = inline_command_with_config(&b"PING\n"[..], &config).unwrap();
+/// assert_eq!(args, vec![b"PING".to_vec()]);
+/// ```
+pub fn inline_command_with_config<'a>(
+    input: &'a [u8],
+    config: &ParseConfig,
+) -> IResult<&'a [u8], Vec<Vec<u8>>, RespError> {
+    inline(input, Context::new(false, *config))
+}
+
+fn inline(input: &[u8], ctx: Context) -> IResult<&[u8], Vec<Vec<u8>>, RespError> {
+    let (input, line) = line(input, ctx)?;
+    let args = line
+        .split(|byte| *byte == b' ')
+        .filter(|arg| !arg.is_empty())
+        .map(<[u8]>::to_vec)
+        .collect::<Vec<_>>();
+    if args.is_empty() {
+        return Err(Err::Error(RespError::InvalidCommand));
+    }
+    Ok((input, args))
+}
+
+/// Builder of client requests, accumulating arguments of different types into an Array of Bulk Strings.
+///
+/// # Examples
+/// ```
+/// use respirator::CommandBuilder;
+///
+/// let bytes = vec![0xff, 0x00];
+/// let command = CommandBuilder::new("SET").arg("key").arg(&bytes).arg(42).build();
+/// assert_eq!(
+///     command.encode(),
+///     b"*4\r\n$3\r\nSET\r\n$3\r\nkey\r\n$2\r\n\xff\x00\r\n$2\r\n42\r\n".to_vec()
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommandBuilder {
+    args: Vec<Vec<u8>>,
+}
+
+impl CommandBuilder {
+    /// Starts request of the given command.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of the command, e.g. `GET`
+    pub fn new(name: impl CommandArg) -> Self {
+        CommandBuilder {
+            args: vec![name.into_arg()],
+        }
+    }
+
+    /// Appends the argument.
+    ///
+    /// # Arguments
+    ///
+    /// * `arg` - the argument, integers are sent as their decimal representation
+    pub fn arg(mut self, arg: impl CommandArg) -> Self {
+        self.args.push(arg.into_arg());
+        self
+    }
+
+    /// Builds the request, ready to be encoded.
+    pub fn build(self) -> Resp {
+        self.args.into_iter().map(Resp::from).collect()
+    }
+}
+
+/// Types which can be sent as command arguments, i.e. as Bulk String payloads.
+pub trait CommandArg {
+    /// Converts the argument into its Bulk String payload.
+    fn into_arg(self) -> Vec<u8>;
+}
+
+impl CommandArg for &str {
+    fn into_arg(self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+impl CommandArg for String {
+    fn into_arg(self) -> Vec<u8> {
+        self.into_bytes()
+    }
+}
+
+impl CommandArg for &String {
+    fn into_arg(self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+impl CommandArg for &[u8] {
+    fn into_arg(self) -> Vec<u8> {
+        self.to_vec()
+    }
+}
+
+impl<const N: usize> CommandArg for &[u8; N] {
+    fn into_arg(self) -> Vec<u8> {
+        self.to_vec()
+    }
+}
+
+impl CommandArg for &Vec<u8> {
+    fn into_arg(self) -> Vec<u8> {
+        self.clone()
+    }
+}
+
+impl CommandArg for Vec<u8> {
+    fn into_arg(self) -> Vec<u8> {
+        self
+    }
+}
+
+/// Integers and floats are sent as their decimal representation.
+macro_rules! numeric_arg {
+    ($($ty:ty),*) => {
+        $(
+            impl CommandArg for $ty {
+                fn into_arg(self) -> Vec<u8> {
+                    self.to_string().into_bytes()
+                }
+            }
+        )*
+    };
+}
+
+numeric_arg!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_inline_commands() {
+        let (input, args) = inline_command(&b"PING\r\nSET  key value \r\n"[..]).unwrap();
+        assert_eq!(args, vec![b"PING".to_vec()]);
+
+        let (input, args) = inline_command(input).unwrap();
+        assert_eq!(
+            args,
+            vec![b"SET".to_vec(), b"key".to_vec(), b"value".to_vec()]
+        );
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_unterminated_inline_command() {
+        let corrupted_input = &b"PING"[..];
+        inline_command(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn rejects_inline_commands_like_other_lines() {
+        assert_eq!(
+            inline_command(&b"PING"[..]),
+            Err(Err::Error(RespError::MissingCrlf))
+        );
+        assert!(inline_command(&b"SET k\rv\r\n"[..]).is_err());
+        assert!(inline_command(&b"PING\n"[..]).is_err());
+
+        let config = ParseConfig::new().lenient_line_endings(true);
+        let (rest, args) = inline_command_with_config(&b"ECHO hi\nPING\n"[..], &config).unwrap();
+        assert_eq!(args, vec![b"ECHO".to_vec(), b"hi".to_vec()]);
+        assert_eq!(rest, b"PING\n");
+    }
+
+    #[test]
+    fn parses_requests_in_both_forms() {
+        let input = &b"*1\r\n$4\r\nPING\r\nECHO hi\r\n"[..];
+        let (input, args) = request(input).unwrap();
+        assert_eq!(args, vec![b"PING".to_vec()]);
+        let (input, args) = request(input).unwrap();
+        assert_eq!(args, vec![b"ECHO".to_vec(), b"hi".to_vec()]);
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn parses_requests_as_they_arrive() {
+        let config = ParseConfig::default();
+        let input = &b"*2\r\n$4\r\nECHO\r\n$2\r\nhi\r\nPING\r\n"[..];
+        for end in 0..input.len() - 6 {
+            assert!(matches!(
+                request_streaming_with_config(&input[..end], &config),
+                Err(Err::Incomplete(_))
+            ));
+        }
+        let (rest, args) = request_streaming_with_config(input, &config).unwrap();
+        assert_eq!(args, vec![b"ECHO".to_vec(), b"hi".to_vec()]);
+        let (rest, args) = request_streaming_with_config(rest, &config).unwrap();
+        assert_eq!(args, vec![b"PING".to_vec()]);
+        assert!(rest.is_empty());
+
+        assert_eq!(
+            request_streaming_with_config(&b"*1\r\n:1\r\n"[..], &config),
+            Err(Err::Error(RespError::InvalidCommand))
+        );
+    }
+
+    #[test]
+    fn limits_requests() {
+        let config = ParseConfig::new().max_array_len(1).max_bulk_len(4);
+        let (_, command) =
+            parse_command_with_config(&b"*1\r\n$4\r\nPING\r\n"[..], &config).unwrap();
+        assert_eq!(command.name, b"PING".to_vec());
+        for input in [
+            &b"*2\r\n$4\r\nECHO\r\n$2\r\nhi\r\n"[..],
+            &b"*1\r\n$5\r\nHELLO\r\n"[..],
+        ] {
+            assert_eq!(
+                request_with_config(input, &config),
+                Err(Err::Error(RespError::LimitExceeded))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_non_command_requests() {
+        for input in [
+            &b"+OK\r\n"[..],
+            &b":1\r\n"[..],
+            &b"-ERR\r\n"[..],
+            &b"$4\r\nPING\r\n"[..],
+            &b"*0\r\n"[..],
+            &b"*1\r\n:1\r\n"[..],
+            &b"\r\n"[..],
+            &b"   \r\n"[..],
+        ] {
+            assert_eq!(request(input), Err(Err::Error(RespError::InvalidCommand)));
+        }
+    }
+
+    #[test]
+    fn parses_commands() {
+        let input = &b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$0\r\n\r\n*1\r\n$4\r\nPING\r\n"[..];
+        let (input, command) = parse_command(input).unwrap();
+        assert_eq!(
+            command,
+            Command {
+                name: b"SET".to_vec(),
+                args: vec![b"key".to_vec(), vec![]]
+            }
+        );
+
+        let (input, command) = parse_command(input).unwrap();
+        assert_eq!(
+            command,
+            Command {
+                name: b"PING".to_vec(),
+                args: vec![]
+            }
+        );
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn fails_on_malformed_commands() {
+        for corrupted_input in [
+            &b"+PING\r\n"[..],
+            &b"*0\r\n"[..],
+            &b"*-1\r\n"[..],
+            &b"*2\r\n$3\r\nGET\r\n:1\r\n"[..],
+            &b"*1\r\n$-1\r\n"[..],
+        ] {
+            if let Err(Err::Error(error)) = parse_command(corrupted_input) {
+                assert_eq!(error, RespError::InvalidCommand);
+            } else {
+                panic!("Error rejecting malformed command");
+            }
+        }
+    }
+
+    #[test]
+    fn builds_commands_with_typed_args() {
+        let key = String::from("key");
+        let command = CommandBuilder::new("INCRBY")
+            .arg(String::from("counter"))
+            .arg(&key)
+            .arg(-8i64)
+            .arg(u64::MAX)
+            .arg(-1i8)
+            .arg(7u16)
+            .arg(2.5f64)
+            .arg(&b"raw"[..])
+            .arg(b"\r\n")
+            .build();
+        let (_, parsed) = parse_command(&command.encode()).unwrap();
+        assert_eq!(parsed.name, b"INCRBY".to_vec());
+        assert_eq!(
+            parsed.args,
+            vec![
+                b"counter".to_vec(),
+                b"key".to_vec(),
+                b"-8".to_vec(),
+                b"18446744073709551615".to_vec(),
+                b"-1".to_vec(),
+                b"7".to_vec(),
+                b"2.5".to_vec(),
+                b"raw".to_vec(),
+                b"\r\n".to_vec(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+/// Default maximum nesting depth of aggregates, generous for any real reply while keeping recursion bounded.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Options of the parser: limits enforced while parsing, protecting against malicious input,
+/// and how strictly the input has to follow the protocol. Defaults suit conformant servers.
+///
+/// # Examples
+/// ```
+/// use respirator::{resp_with_config, ParseConfig};
+///
+/// let config = ParseConfig::new().max_depth(1).lenient_line_endings(true);
+/// assert!(resp_with_config(&b"*1\n:1\n"[..], &config).is_ok());
+/// assert!(resp_with_config(&b"*1\r\n*1\r\n:1\r\n"[..], &config).is_err());
+///
+/// let config = ParseConfig { max_bulk_len: 512, ..ParseConfig::default() };
+/// assert!(resp_with_config(&b"$1024\r\n"[..], &config).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParseConfig {
+    /// Maximum number of nested aggregates (Arrays, Maps, Sets and Pushes), 0 disallows aggregates at all.
+    /// Parsing itself doesn't recurse, but dropping, encoding or displaying the parsed value does, so the limit bounds their stack usage.
+    pub max_depth: usize,
+    /// Maximum number of elements of an Array, Set or Push, checked before any element is parsed.
+    pub max_array_len: usize,
+    /// Maximum number of entries of a Map, checked before any entry is parsed.
+    pub max_map_len: usize,
+    /// Maximum declared length of a Bulk String, Bulk Error or Verbatim String, checked before its payload is read.
+    pub max_bulk_len: usize,
+    /// Handling of Integers not fitting into i64.
+    pub integer_overflow: IntegerOverflow,
+    /// Whether Simple String and Error payloads must be valid UTF-8, failing with `RespError::InvalidUtf8` otherwise.
+    pub strict_utf8: bool,
+    /// Whether lines and payloads may be terminated with a bare LF instead of CRLF, as some non-conformant tools do.
+    pub lenient_line_endings: bool,
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        ParseConfig {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_array_len: usize::MAX,
+            max_map_len: usize::MAX,
+            max_bulk_len: usize::MAX,
+            integer_overflow: IntegerOverflow::default(),
+            strict_utf8: false,
+            lenient_line_endings: false,
+        }
+    }
+}
+
+impl ParseConfig {
+    /// Creates default configuration, same as [`ParseConfig::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets maximum number of nested aggregates.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_depth` - the maximum depth, 0 disallows aggregates at all
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets maximum number of elements of an Array, Set or Push.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_array_len` - the maximum number of elements
+    pub fn max_array_len(mut self, max_array_len: usize) -> Self {
+        self.max_array_len = max_array_len;
+        self
+    }
+
+    /// Sets maximum number of entries of a Map.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_map_len` - the maximum number of entries
+    pub fn max_map_len(mut self, max_map_len: usize) -> Self {
+        self.max_map_len = max_map_len;
+        self
+    }
+
+    /// Sets maximum length of a Bulk String, Bulk Error or Verbatim String.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_bulk_len` - the maximum length in bytes
+    pub fn max_bulk_len(mut self, max_bulk_len: usize) -> Self {
+        self.max_bulk_len = max_bulk_len;
+        self
+    }
+
+    /// Sets handling of Integers not fitting into i64.
+    ///
+    /// # Arguments
+    ///
+    /// * `integer_overflow` - the handling of overflowing Integers
+    pub fn integer_overflow(mut self, integer_overflow: IntegerOverflow) -> Self {
+        self.integer_overflow = integer_overflow;
+        self
+    }
+
+    /// Sets whether Simple String and Error payloads must be valid UTF-8.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict_utf8` - whether to validate UTF-8
+    pub fn strict_utf8(mut self, strict_utf8: bool) -> Self {
+        self.strict_utf8 = strict_utf8;
+        self
+    }
+
+    /// Sets whether lines and payloads may be terminated with a bare LF.
+    ///
+    /// # Arguments
+    ///
+    /// * `lenient_line_endings` - whether to accept bare LF
+    pub fn lenient_line_endings(mut self, lenient_line_endings: bool) -> Self {
+        self.lenient_line_endings = lenient_line_endings;
+        self
+    }
+}
+
+/// Handling of Integer payloads that are valid signed decimals, but do not fit into i64.
+///
+/// # Examples
+/// ```
+/// use respirator::{resp_with_config, IntegerOverflow, ParseConfig, Resp};
+///
+/// let config = ParseConfig { integer_overflow: IntegerOverflow::Saturate, ..ParseConfig::default() };
+/// let (_, parsed) = resp_with_config(&b":9223372036854775808\r\n"[..], &config).unwrap();
+/// assert_eq!(parsed, Resp::Integer(i64::MAX));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IntegerOverflow {
+    /// Fail with `RespError::IntegerOverflow`.
+    #[default]
+    Error,
+    /// Clamp to `i64::MAX` or `i64::MIN`, depending on the sign.
+    Saturate,
+}
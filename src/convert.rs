@@ -0,0 +1,478 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{error, fmt, str::Utf8Error};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::Resp;
+
+/// Error returned when `Resp` can't be converted into a Rust type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConversionError {
+    /// Variant of the value can't be converted into requested type.
+    WrongType,
+    /// Value is a string, but it's not a valid decimal integer.
+    InvalidInteger,
+    /// Value is a string, but it's not a valid UTF-8.
+    InvalidUtf8(Utf8Error),
+    /// Array of alternating keys and values has an odd number of elements.
+    OddLength,
+    /// Element of an aggregate at the given index can't be converted.
+    Element {
+        /// Index of the offending element.
+        index: usize,
+        /// Why the element can't be converted.
+        error: Box<ConversionError>,
+    },
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionError::WrongType => write!(f, "value is of a wrong type"),
+            ConversionError::InvalidInteger => write!(f, "value is not a valid integer"),
+            ConversionError::InvalidUtf8(_) => write!(f, "value is not a valid UTF-8"),
+            ConversionError::OddLength => write!(f, "array has an odd number of elements"),
+            ConversionError::Element { index, error } => write!(f, "element {}: {}", index, error),
+        }
+    }
+}
+
+impl error::Error for ConversionError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ConversionError::InvalidUtf8(error) => Some(error),
+            ConversionError::Element { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Resp {
+    /// Converts map-shaped reply, i.e. RESP3 `Map` or RESP2 `Array` of alternating keys and values
+    /// (as returned by e.g. `HGETALL`), into a `HashMap`. Keys must be `SimpleString` or non-null `BulkString`,
+    /// the first one wins if keys repeat, as in [`map_get`](Resp::map_get).
+    ///
+    /// Fails with [`ConversionError`] rather than `RespError`, like the other conversions, as the value itself is valid RESP,
+    /// just not map-shaped. Only available with the `std` feature, as `HashMap` is not part of `alloc`.
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// let (_, parsed) = respirator::resp(&b"*4\r\n$1\r\na\r\n:1\r\n$1\r\nb\r\n:2\r\n"[..]).unwrap();
+    /// let map = parsed.into_map().unwrap();
+    /// assert_eq!(map[&b"b"[..]], Resp::Integer(2));
+    /// ```
+    pub fn into_map(self) -> Result<HashMap<Vec<u8>, Resp>, ConversionError> {
+        let entries = match self {
+            Resp::Map(entries) => entries,
+            Resp::Array(Some(values)) => {
+                if values.len() % 2 != 0 {
+                    return Err(ConversionError::OddLength);
+                }
+                let mut values = values.into_iter();
+                let mut entries = Vec::with_capacity(values.len() / 2);
+                while let (Some(key), Some(value)) = (values.next(), values.next()) {
+                    entries.push((key, value));
+                }
+                entries
+            }
+            _ => return Err(ConversionError::WrongType),
+        };
+        let mut map = HashMap::with_capacity(entries.len());
+        for (key, value) in entries {
+            match key {
+                Resp::SimpleString(key) | Resp::BulkString(Some(key)) => {
+                    map.entry(key).or_insert(value);
+                }
+                _ => return Err(ConversionError::WrongType),
+            }
+        }
+        Ok(map)
+    }
+}
+
+impl Resp {
+    /// Builds `Error` reply out of its code and message, separated by a space.
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// let error = Resp::error("ERR", "unknown command");
+    /// assert_eq!(error.encode(), b"-ERR unknown command\r\n".to_vec());
+    /// ```
+    pub fn error(code: &str, message: &str) -> Self {
+        let mut val = Vec::with_capacity(code.len() + 1 + message.len());
+        val.extend_from_slice(code.as_bytes());
+        val.push(b' ');
+        val.extend_from_slice(message.as_bytes());
+        Resp::Error(val)
+    }
+
+    /// Builds null `BulkString` (`$-1`), as opposed to [`empty_bulk`](Resp::empty_bulk).
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// assert_eq!(Resp::null_bulk().encode(), b"$-1\r\n".to_vec());
+    /// ```
+    pub fn null_bulk() -> Self {
+        Resp::BulkString(None)
+    }
+
+    /// Builds empty `BulkString` (`$0`), as opposed to [`null_bulk`](Resp::null_bulk).
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// assert_eq!(Resp::empty_bulk().encode(), b"$0\r\n\r\n".to_vec());
+    /// ```
+    pub fn empty_bulk() -> Self {
+        Resp::BulkString(Some(Vec::new()))
+    }
+
+    /// Builds null `Array` (`*-1`), as opposed to [`empty_array`](Resp::empty_array).
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// assert_eq!(Resp::null_array().encode(), b"*-1\r\n".to_vec());
+    /// ```
+    pub fn null_array() -> Self {
+        Resp::Array(None)
+    }
+
+    /// Builds empty `Array` (`*0`), as opposed to [`null_array`](Resp::null_array).
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// assert_eq!(Resp::empty_array().encode(), b"*0\r\n".to_vec());
+    /// ```
+    pub fn empty_array() -> Self {
+        Resp::Array(Some(Vec::new()))
+    }
+}
+
+/// Converts `Integer`, or `SimpleString` and `BulkString` holding a decimal integer.
+///
+/// # Examples
+/// ```
+/// use respirator::Resp;
+///
+/// assert_eq!(i64::try_from(Resp::Integer(8)), Ok(8));
+/// assert_eq!(i64::try_from(Resp::BulkString(Some(b"-12".to_vec()))), Ok(-12));
+/// assert!(i64::try_from(Resp::Null).is_err());
+/// ```
+impl TryFrom<Resp> for i64 {
+    type Error = ConversionError;
+
+    fn try_from(value: Resp) -> Result<Self, Self::Error> {
+        match value {
+            Resp::Integer(val) => Ok(val),
+            Resp::SimpleString(val) | Resp::BulkString(Some(val)) => core::str::from_utf8(&val)
+                .ok()
+                .and_then(|val| val.parse().ok())
+                .ok_or(ConversionError::InvalidInteger),
+            _ => Err(ConversionError::WrongType),
+        }
+    }
+}
+
+/// Converts `SimpleString`, `Error`, `BulkString` and `VerbatimString` (without its format), validating UTF-8 strictly.
+///
+/// # Examples
+/// ```
+/// use respirator::Resp;
+///
+/// assert_eq!(String::try_from(Resp::SimpleString(b"OK".to_vec())), Ok("OK".to_string()));
+/// assert!(String::try_from(Resp::BulkString(Some(b"\xff".to_vec()))).is_err());
+/// assert!(String::try_from(Resp::BulkString(None)).is_err());
+/// ```
+impl TryFrom<Resp> for String {
+    type Error = ConversionError;
+
+    fn try_from(value: Resp) -> Result<Self, Self::Error> {
+        match value {
+            Resp::SimpleString(val)
+            | Resp::Error(val)
+            | Resp::BulkString(Some(val))
+            | Resp::VerbatimString { data: val, .. } => String::from_utf8(val)
+                .map_err(|error| ConversionError::InvalidUtf8(error.utf8_error())),
+            _ => Err(ConversionError::WrongType),
+        }
+    }
+}
+
+/// Converts `Array` or `Set` of values convertible into `String`, e.g. a reply to `KEYS` or `SMEMBERS`.
+/// Null `Array` is converted into an empty `Vec`, as it's returned when there are no elements to reply with.
+///
+/// # Examples
+/// ```
+/// use respirator::{ConversionError, Resp};
+///
+/// let (_, parsed) = respirator::resp(&b"*2\r\n$1\r\na\r\n$1\r\nb\r\n"[..]).unwrap();
+/// assert_eq!(Vec::<String>::try_from(parsed), Ok(vec!["a".to_string(), "b".to_string()]));
+///
+/// let mixed = Resp::Array(Some(vec!["a".into(), Resp::Integer(1)]));
+/// assert_eq!(
+///     Vec::<String>::try_from(mixed),
+///     Err(ConversionError::Element { index: 1, error: Box::new(ConversionError::WrongType) })
+/// );
+/// ```
+impl TryFrom<Resp> for Vec<String> {
+    type Error = ConversionError;
+
+    fn try_from(value: Resp) -> Result<Self, Self::Error> {
+        let values = match value {
+            Resp::Array(Some(values)) | Resp::Set(values) => values,
+            Resp::Array(None) => return Ok(Vec::new()),
+            _ => return Err(ConversionError::WrongType),
+        };
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                String::try_from(value).map_err(|error| ConversionError::Element {
+                    index,
+                    error: Box::new(error),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Builds `Integer`.
+///
+/// # Examples
+/// ```
+/// use respirator::Resp;
+///
+/// assert_eq!(Resp::from(8), Resp::Integer(8));
+/// ```
+impl From<i64> for Resp {
+    fn from(value: i64) -> Self {
+        Resp::Integer(value)
+    }
+}
+
+/// Builds `BulkString`, the type in which clients send commands and their arguments.
+/// Use `Resp::SimpleString` directly for status replies.
+///
+/// # Examples
+/// ```
+/// use respirator::Resp;
+///
+/// let get: Resp = Resp::Array(Some(vec!["GET".into(), "key".into()]));
+/// assert_eq!(get.encode(), b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n".to_vec());
+/// ```
+impl From<&str> for Resp {
+    fn from(value: &str) -> Self {
+        Resp::BulkString(Some(value.as_bytes().to_vec()))
+    }
+}
+
+/// Builds `BulkString`, as binary safe type.
+///
+/// # Examples
+/// ```
+/// use respirator::Resp;
+///
+/// assert_eq!(Resp::from(b"\x00\xff".to_vec()), Resp::BulkString(Some(b"\x00\xff".to_vec())));
+/// ```
+impl From<Vec<u8>> for Resp {
+    fn from(value: Vec<u8>) -> Self {
+        Resp::BulkString(Some(value))
+    }
+}
+
+/// Collects values into `Array`. Collecting an empty iterator yields an empty array, i.e. `Array(Some(vec![]))`,
+/// not a null one.
+///
+/// # Examples
+/// ```
+/// use respirator::Resp;
+///
+/// let key = "key";
+/// let command: Resp = ["GET".into(), key.into()].into_iter().collect();
+/// assert_eq!(command.encode(), b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n".to_vec());
+/// ```
+impl FromIterator<Resp> for Resp {
+    fn from_iter<I: IntoIterator<Item = Resp>>(iter: I) -> Self {
+        Resp::Array(Some(iter.into_iter().collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distinguishes_null_from_empty() {
+        assert!(Resp::null_bulk().is_null());
+        assert!(Resp::null_array().is_null());
+        assert!(!Resp::empty_bulk().is_null());
+        assert!(!Resp::empty_array().is_null());
+        assert_eq!(Resp::empty_bulk().as_bulk_string(), Some(&b""[..]));
+        assert_eq!(Resp::empty_array().as_array(), Some(&[][..]));
+    }
+
+    #[test]
+    fn converts_into_strings() {
+        let set = Resp::Set(vec!["a".into(), Resp::SimpleString(b"b".to_vec())]);
+        assert_eq!(
+            Vec::<String>::try_from(set),
+            Ok(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(Vec::<String>::try_from(Resp::Array(None)), Ok(vec![]));
+        assert_eq!(
+            Vec::<String>::try_from(Resp::Map(vec![])),
+            Err(ConversionError::WrongType)
+        );
+
+        let invalid = Resp::Array(Some(vec!["a".into(), "b".into(), b"\xff".to_vec().into()]));
+        let error = Vec::<String>::try_from(invalid).unwrap_err();
+        assert!(matches!(
+            &error,
+            ConversionError::Element { index: 2, error } if matches!(**error, ConversionError::InvalidUtf8(_))
+        ));
+        assert_eq!(error.to_string(), "element 2: value is not a valid UTF-8");
+    }
+
+    #[test]
+    fn converts_into_i64() {
+        assert_eq!(i64::try_from(Resp::Integer(-8)), Ok(-8));
+        assert_eq!(i64::try_from(Resp::SimpleString(b"42".to_vec())), Ok(42));
+        assert_eq!(
+            i64::try_from(Resp::BulkString(Some(b"12345".to_vec()))),
+            Ok(12345)
+        );
+    }
+
+    #[test]
+    fn fails_converting_into_i64() {
+        assert_eq!(
+            i64::try_from(Resp::BulkString(Some(b"12a".to_vec()))),
+            Err(ConversionError::InvalidInteger)
+        );
+        assert_eq!(
+            i64::try_from(Resp::BulkString(None)),
+            Err(ConversionError::WrongType)
+        );
+        assert_eq!(
+            i64::try_from(Resp::Array(Some(vec![Resp::Integer(1)]))),
+            Err(ConversionError::WrongType)
+        );
+    }
+
+    #[test]
+    fn converts_into_string() {
+        assert_eq!(
+            String::try_from(Resp::Error(b"ERR".to_vec())),
+            Ok("ERR".to_string())
+        );
+        assert_eq!(
+            String::try_from(Resp::BulkString(Some("zażółć".as_bytes().to_vec()))),
+            Ok("zażółć".to_string())
+        );
+        assert_eq!(
+            String::try_from(Resp::VerbatimString {
+                format: *b"txt",
+                data: b"Some string".to_vec()
+            }),
+            Ok("Some string".to_string())
+        );
+    }
+
+    #[test]
+    fn fails_converting_into_string() {
+        assert!(matches!(
+            String::try_from(Resp::SimpleString(b"a\xffb".to_vec())),
+            Err(ConversionError::InvalidUtf8(_))
+        ));
+        assert_eq!(
+            String::try_from(Resp::Integer(8)),
+            Err(ConversionError::WrongType)
+        );
+        assert_eq!(
+            String::try_from(Resp::Array(None)),
+            Err(ConversionError::WrongType)
+        );
+        assert_eq!(
+            String::try_from(Resp::Null),
+            Err(ConversionError::WrongType)
+        );
+    }
+
+    #[test]
+    fn converts_into_map() {
+        let flat = Resp::Array(Some(vec![
+            "a".into(),
+            Resp::Integer(1),
+            Resp::SimpleString(b"b".to_vec()),
+            Resp::Null,
+            "a".into(),
+            Resp::Integer(3),
+        ]));
+        let first = flat.map_get(b"a").cloned();
+        let map = flat.into_map().unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&b"a"[..]], Resp::Integer(1));
+        assert_eq!(map.get(&b"a"[..]).cloned(), first);
+        assert_eq!(map[&b"b"[..]], Resp::Null);
+
+        let (_, parsed) = crate::resp(&b"%1\r\n+key\r\n$5\r\nvalue\r\n"[..]).unwrap();
+        let map = parsed.into_map().unwrap();
+        assert_eq!(map[&b"key"[..]], Resp::from("value"));
+
+        assert!(Resp::Array(Some(vec![])).into_map().unwrap().is_empty());
+    }
+
+    #[test]
+    fn fails_converting_into_map() {
+        assert_eq!(
+            Resp::Array(Some(vec!["a".into()])).into_map(),
+            Err(ConversionError::OddLength)
+        );
+        assert_eq!(
+            Resp::Array(Some(vec![Resp::Integer(1), Resp::Integer(2)])).into_map(),
+            Err(ConversionError::WrongType)
+        );
+        assert_eq!(
+            Resp::Array(None).into_map(),
+            Err(ConversionError::WrongType)
+        );
+        assert_eq!(
+            Resp::Set(vec![]).into_map(),
+            Err(ConversionError::WrongType)
+        );
+    }
+
+    #[test]
+    fn converts_from_rust_types() {
+        assert_eq!(Resp::from(-8), Resp::Integer(-8));
+        assert_eq!(Resp::from("GET"), Resp::BulkString(Some(b"GET".to_vec())));
+        assert_eq!(
+            Resp::from(b"key".to_vec()),
+            Resp::BulkString(Some(b"key".to_vec()))
+        );
+    }
+
+    #[test]
+    fn collects_into_array() {
+        let array: Resp = (1..=2).map(Resp::from).collect();
+        assert_eq!(
+            array,
+            Resp::Array(Some(vec![Resp::Integer(1), Resp::Integer(2)]))
+        );
+
+        let empty: Resp = std::iter::empty().collect();
+        assert_eq!(empty, Resp::Array(Some(vec![])));
+    }
+}
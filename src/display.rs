@@ -0,0 +1,133 @@
+use alloc::{borrow::Cow, string::String};
+use core::fmt;
+
+use crate::Resp;
+
+const INDENT: &str = "  ";
+
+/// Human-readable representation of RESP values, meant for CLI output.
+/// Byte payloads are lossily decoded as UTF-8, aggregates print their length and list their elements
+/// indented one level deeper, one per line.
+///
+/// # Examples
+/// ```
+/// let (_, parsed) = respirator::resp(&b"*2\r\n+OK\r\n:8\r\n"[..]).unwrap();
+/// assert_eq!(parsed.to_string(), "Array(2)\n  SimpleString(\"OK\")\n  Integer(8)");
+/// ```
+impl fmt::Display for Resp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl Resp {
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        match self {
+            Resp::SimpleString(val) => write!(f, "SimpleString({:?})", lossy(val)),
+            Resp::Integer(val) => write!(f, "Integer({})", val),
+            Resp::Error(val) => write!(f, "Error({:?})", lossy(val)),
+            Resp::BulkString(Some(val)) => write!(f, "BulkString({:?})", lossy(val)),
+            Resp::BulkString(None) => write!(f, "BulkString(nil)"),
+            Resp::Array(Some(values)) => fmt_elements(f, "Array", values, depth),
+            Resp::Array(None) => write!(f, "Array(nil)"),
+            Resp::Double(val) => write!(f, "Double({})", val),
+            Resp::Boolean(val) => write!(f, "Boolean({})", val),
+            Resp::Null => write!(f, "Null"),
+            Resp::Map(entries) => fmt_entries(f, "Map", entries, depth),
+            Resp::Set(values) => fmt_elements(f, "Set", values, depth),
+            Resp::Push(values) => fmt_elements(f, "Push", values, depth),
+            Resp::BigNumber(val) => write!(f, "BigNumber({})", lossy(val)),
+            Resp::VerbatimString { format, data } => {
+                write!(f, "VerbatimString({}, {:?})", lossy(format), lossy(data))
+            }
+            Resp::BulkError(val) => write!(f, "BulkError({:?})", lossy(val)),
+            Resp::Attribute { attributes, value } => {
+                fmt_entries(f, "Attribute", attributes, depth)?;
+                newline(f, depth)?;
+                value.fmt_indented(f, depth)
+            }
+        }
+    }
+}
+
+fn fmt_elements(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    values: &[Resp],
+    depth: usize,
+) -> fmt::Result {
+    write!(f, "{}({})", name, values.len())?;
+    for value in values {
+        newline(f, depth + 1)?;
+        value.fmt_indented(f, depth + 1)?;
+    }
+    Ok(())
+}
+
+fn fmt_entries(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    entries: &[(Resp, Resp)],
+    depth: usize,
+) -> fmt::Result {
+    write!(f, "{}({})", name, entries.len())?;
+    for (key, value) in entries {
+        newline(f, depth + 1)?;
+        key.fmt_indented(f, depth + 1)?;
+        write!(f, " => ")?;
+        value.fmt_indented(f, depth + 1)?;
+    }
+    Ok(())
+}
+
+fn newline(f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+    writeln!(f)?;
+    for _ in 0..depth {
+        f.write_str(INDENT)?;
+    }
+    Ok(())
+}
+
+fn lossy(val: &[u8]) -> Cow<'_, str> {
+    String::from_utf8_lossy(val)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{resp, Resp};
+
+    #[test]
+    fn displays_scalars() {
+        assert_eq!(
+            Resp::SimpleString(b"OK".to_vec()).to_string(),
+            "SimpleString(\"OK\")"
+        );
+        assert_eq!(Resp::Integer(8).to_string(), "Integer(8)");
+        assert_eq!(Resp::BulkString(None).to_string(), "BulkString(nil)");
+        assert_eq!(
+            Resp::BulkString(Some(b"a\xffb".to_vec())).to_string(),
+            "BulkString(\"a\u{FFFD}b\")"
+        );
+        assert_eq!(Resp::Null.to_string(), "Null");
+    }
+
+    #[test]
+    fn displays_nested_aggregates() {
+        let input = &b"*2\r\n%1\r\n+a\r\n*1\r\n:1\r\n$2\r\nOK\r\n"[..];
+        let (_, parsed) = resp(input).unwrap();
+        assert_eq!(
+            parsed.to_string(),
+            "Array(2)\n  Map(1)\n    SimpleString(\"a\") => Array(1)\n      Integer(1)\n  BulkString(\"OK\")"
+        );
+    }
+
+    #[test]
+    fn displays_attribute_before_value() {
+        let input = &b"|1\r\n+ttl\r\n:3600\r\n+OK\r\n"[..];
+        let (_, parsed) = resp(input).unwrap();
+        assert_eq!(
+            parsed.to_string(),
+            "Attribute(1)\n  SimpleString(\"ttl\") => Integer(3600)\nSimpleString(\"OK\")"
+        );
+    }
+}
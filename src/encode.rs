@@ -0,0 +1,626 @@
+use alloc::{vec, vec::Vec};
+use core::{
+    convert::Infallible,
+    fmt::{self, Write},
+};
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::{parser::is_signed_decimal, Resp, RespError};
+
+const CRLF: &[u8] = b"\r\n";
+
+impl Resp {
+    /// Serializes RESP value back into its wire representation.
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::{Resp, resp};
+    ///
+    /// let input = &b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n"[..];
+    /// let (_, parsed) = resp(input).unwrap();
+    /// assert_eq!(parsed.encode(), input.to_vec());
+    /// ```
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode_into(&mut buf);
+        buf
+    }
+
+    /// Serializes RESP value, appending it to the buffer, which lets the caller reuse one buffer for many values.
+    /// Bytes already in the buffer are kept, clearing it is up to the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - a buffer to append serialized value to
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// let mut buf = Vec::new();
+    /// Resp::SimpleString(b"OK".to_vec()).encode_into(&mut buf);
+    /// Resp::Integer(8).encode_into(&mut buf);
+    /// assert_eq!(buf, b"+OK\r\n:8\r\n".to_vec());
+    /// ```
+    pub fn encode_into(&self, buf: &mut Vec<u8>) {
+        match self.write_encoded(buf) {
+            Ok(()) => {}
+            Err(infallible) => match infallible {},
+        }
+    }
+
+    /// Serializes RESP value directly into a writer (e.g. `TcpStream` or `BufWriter`),
+    /// without building an intermediate buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `w` - a writer to serialize into
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// let mut out = Vec::new();
+    /// Resp::Array(Some(vec![Resp::Integer(8)])).encode_to(&mut out).unwrap();
+    /// assert_eq!(out, b"*1\r\n:8\r\n".to_vec());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_encoded(&mut Writer(w))
+    }
+
+    /// Computes the number of bytes [`Resp::encode`] would produce, without serializing into a buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// let value = Resp::Array(Some(vec![Resp::BulkString(Some(b"key".to_vec())), Resp::Integer(-8)]));
+    /// assert_eq!(value.encoded_len(), value.encode().len());
+    /// ```
+    pub fn encoded_len(&self) -> usize {
+        let mut counter = Counter(0);
+        match self.write_encoded(&mut counter) {
+            Ok(()) => counter.0,
+            Err(infallible) => match infallible {},
+        }
+    }
+
+    /// Checks whether value can be encoded into valid RESP, which values built by hand may violate,
+    /// reporting the first violation in encoding order: `InvalidLine` if a Simple String or Error contains CR or LF,
+    /// `InvalidBigNumber` if a Big Number is not a signed decimal.
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::{Resp, RespError};
+    ///
+    /// assert_eq!(Resp::SimpleString(b"OK".to_vec()).validate(), Ok(()));
+    /// let value = Resp::Array(Some(vec![Resp::Integer(1), Resp::SimpleString(b"O\r\nK".to_vec())]));
+    /// assert_eq!(value.validate(), Err(RespError::InvalidLine));
+    /// ```
+    pub fn validate(&self) -> Result<(), RespError> {
+        // Walks the tree with an explicit stack, so that deep nesting can't overflow the native one.
+        let mut pending = vec![self];
+        while let Some(value) = pending.pop() {
+            match value {
+                Resp::SimpleString(val) | Resp::Error(val)
+                    if val.iter().any(|byte| matches!(byte, b'\r' | b'\n')) =>
+                {
+                    return Err(RespError::InvalidLine);
+                }
+                Resp::BigNumber(val) if !is_signed_decimal(val) => {
+                    return Err(RespError::InvalidBigNumber);
+                }
+                Resp::Array(Some(values)) | Resp::Set(values) | Resp::Push(values) => {
+                    pending.extend(values.iter().rev());
+                }
+                Resp::Map(entries) => {
+                    pending.extend(entries.iter().rev().flat_map(|(key, value)| [value, key]));
+                }
+                Resp::Attribute { attributes, value } => {
+                    pending.push(value);
+                    pending.extend(
+                        attributes
+                            .iter()
+                            .rev()
+                            .flat_map(|(key, value)| [value, key]),
+                    );
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn write_encoded<O: Output>(&self, out: &mut O) -> Result<(), O::Error> {
+        match self {
+            Resp::SimpleString(val) => write_line(out, b'+', val),
+            Resp::Integer(val) => write_line(out, b':', Decimal::signed(*val).as_bytes()),
+            Resp::Error(val) => write_line(out, b'-', val),
+            Resp::BulkString(Some(val)) => write_blob(out, b'$', val),
+            Resp::BulkString(None) => write_line(out, b'$', b"-1"),
+            Resp::Array(Some(values)) => write_elements(out, b'*', values),
+            Resp::Array(None) => write_line(out, b'*', b"-1"),
+            Resp::Double(val) => write_double(out, *val),
+            Resp::Boolean(true) => write_line(out, b'#', b"t"),
+            Resp::Boolean(false) => write_line(out, b'#', b"f"),
+            Resp::Null => write_line(out, b'_', b""),
+            Resp::Map(entries) => write_entries(out, b'%', entries),
+            Resp::Set(values) => write_elements(out, b'~', values),
+            Resp::Push(values) => write_elements(out, b'>', values),
+            Resp::BigNumber(val) => write_line(out, b'(', val),
+            Resp::VerbatimString { format, data } => {
+                write_line(out, b'=', Decimal::unsigned(data.len() + 4).as_bytes())?;
+                out.put(format)?;
+                out.put(b":")?;
+                out.put(data)?;
+                out.put(CRLF)
+            }
+            Resp::BulkError(val) => write_blob(out, b'!', val),
+            Resp::Attribute { attributes, value } => {
+                write_entries(out, b'|', attributes)?;
+                value.write_encoded(out)
+            }
+        }
+    }
+}
+
+/// Serializes Array header (`*<len>\r\n`), which, followed by `len` encoded elements, forms an Array
+/// without building the whole [`Resp::Array`] in memory. See [`ArrayEncoder`] for a variant checking the element count.
+///
+/// # Arguments
+///
+/// * `len` - the number of elements to follow
+///
+/// # Examples
+/// ```
+/// use respirator::{encode_array_header, resp, Resp};
+///
+/// let mut buf = encode_array_header(2);
+/// Resp::Integer(1).encode_into(&mut buf);
+/// Resp::Integer(2).encode_into(&mut buf);
+/// let (_, parsed) = resp(&buf).unwrap();
+/// assert_eq!(parsed, Resp::Array(Some(vec![Resp::Integer(1), Resp::Integer(2)])));
+/// ```
+pub fn encode_array_header(len: usize) -> Vec<u8> {
+    let mut buf = Vec::new();
+    match write_line(&mut buf, b'*', Decimal::unsigned(len).as_bytes()) {
+        Ok(()) => buf,
+        Err(infallible) => match infallible {},
+    }
+}
+
+/// Serializes Array element by element, keeping track of how many of the declared elements have been written.
+/// Buffer is passed to every call, so the caller can flush it between elements.
+///
+/// # Examples
+/// ```
+/// use respirator::{ArrayEncoder, Resp};
+///
+/// let mut buf = Vec::new();
+/// let mut encoder = ArrayEncoder::new(2, &mut buf);
+/// for val in 1..=2 {
+///     encoder.encode(&Resp::Integer(val), &mut buf);
+/// }
+/// encoder.finish();
+/// assert_eq!(buf, b"*2\r\n:1\r\n:2\r\n".to_vec());
+/// ```
+#[derive(Debug)]
+#[must_use = "call `finish` to check all declared elements have been written"]
+pub struct ArrayEncoder {
+    declared: usize,
+    written: usize,
+}
+
+impl ArrayEncoder {
+    /// Starts Array of `len` elements, appending its header to the buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `len` - the number of elements to be written
+    /// * `buf` - a buffer to append the header to
+    pub fn new(len: usize, buf: &mut Vec<u8>) -> Self {
+        buf.extend_from_slice(&encode_array_header(len));
+        ArrayEncoder {
+            declared: len,
+            written: 0,
+        }
+    }
+
+    /// Appends the next element to the buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the element
+    /// * `buf` - a buffer to append the element to
+    ///
+    /// # Panics
+    ///
+    /// Panics if all declared elements have already been written.
+    pub fn encode(&mut self, value: &Resp, buf: &mut Vec<u8>) {
+        assert!(
+            self.written < self.declared,
+            "array of {} elements is already complete",
+            self.declared
+        );
+        value.encode_into(buf);
+        self.written += 1;
+    }
+
+    /// Returns the number of elements yet to be written.
+    pub fn remaining(&self) -> usize {
+        self.declared - self.written
+    }
+
+    /// Completes the Array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer elements than declared have been written.
+    pub fn finish(self) {
+        assert_eq!(
+            self.written, self.declared,
+            "array declared {} elements, but {} were written",
+            self.declared, self.written
+        );
+    }
+}
+
+/// Destination of serialized bytes, lets the encoder target both infallible in-memory buffers
+/// and fallible `io::Write` implementations.
+trait Output {
+    type Error;
+
+    fn put(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+impl Output for Vec<u8> {
+    type Error = Infallible;
+
+    fn put(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+struct Counter(usize);
+
+impl Output for Counter {
+    type Error = Infallible;
+
+    fn put(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.0 += bytes.len();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+struct Writer<'a, W>(&'a mut W);
+
+#[cfg(feature = "std")]
+impl<W: io::Write> Output for Writer<'_, W> {
+    type Error = io::Error;
+
+    fn put(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.0.write_all(bytes)
+    }
+}
+
+fn write_line<O: Output>(out: &mut O, type_byte: u8, val: &[u8]) -> Result<(), O::Error> {
+    out.put(&[type_byte])?;
+    out.put(val)?;
+    out.put(CRLF)
+}
+
+fn write_blob<O: Output>(out: &mut O, type_byte: u8, val: &[u8]) -> Result<(), O::Error> {
+    write_line(out, type_byte, Decimal::unsigned(val.len()).as_bytes())?;
+    out.put(val)?;
+    out.put(CRLF)
+}
+
+fn write_elements<O: Output>(out: &mut O, type_byte: u8, values: &[Resp]) -> Result<(), O::Error> {
+    write_line(out, type_byte, Decimal::unsigned(values.len()).as_bytes())?;
+    for value in values {
+        value.write_encoded(out)?;
+    }
+    Ok(())
+}
+
+fn write_entries<O: Output>(
+    out: &mut O,
+    type_byte: u8,
+    entries: &[(Resp, Resp)],
+) -> Result<(), O::Error> {
+    write_line(out, type_byte, Decimal::unsigned(entries.len()).as_bytes())?;
+    for (key, value) in entries {
+        key.write_encoded(out)?;
+        value.write_encoded(out)?;
+    }
+    Ok(())
+}
+
+/// Formats Double straight into the output, so that e.g. [`Resp::encoded_len`] doesn't allocate.
+fn write_double<O: Output>(out: &mut O, val: f64) -> Result<(), O::Error> {
+    out.put(b",")?;
+    if val.is_nan() {
+        out.put(b"nan")?;
+    } else {
+        let mut formatter = Formatter { out, error: None };
+        // Formatting f64 itself can't fail, only the output can.
+        if write!(formatter, "{}", val).is_err() {
+            if let Some(error) = formatter.error {
+                return Err(error);
+            }
+        }
+    }
+    out.put(CRLF)
+}
+
+/// Adapter letting `core::fmt` write into an [`Output`], keeping the error it fails with.
+struct Formatter<'a, O: Output> {
+    out: &'a mut O,
+    error: Option<O::Error>,
+}
+
+impl<O: Output> fmt::Write for Formatter<'_, O> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.out.put(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Stack allocated decimal representation of an integer, long enough for `i64::MIN` and `u64::MAX`.
+struct Decimal {
+    buf: [u8; 20],
+    start: usize,
+}
+
+impl Decimal {
+    fn unsigned(val: usize) -> Self {
+        Self::format(val as u64, false)
+    }
+
+    fn signed(val: i64) -> Self {
+        Self::format(val.unsigned_abs(), val < 0)
+    }
+
+    fn format(mut val: u64, negative: bool) -> Self {
+        let mut decimal = Decimal {
+            buf: [0; 20],
+            start: 20,
+        };
+        loop {
+            decimal.start -= 1;
+            decimal.buf[decimal.start] = b'0' + (val % 10) as u8;
+            val /= 10;
+            if val == 0 {
+                break;
+            }
+        }
+        if negative {
+            decimal.start -= 1;
+            decimal.buf[decimal.start] = b'-';
+        }
+        decimal
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[self.start..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::resp;
+
+    /// Payload of line-based values, which cannot contain CR nor LF.
+    fn line() -> impl Strategy<Value = Vec<u8>> {
+        prop::collection::vec(
+            any::<u8>().prop_filter("line ending", |b| *b != b'\r' && *b != b'\n'),
+            0..16,
+        )
+    }
+
+    fn blob() -> impl Strategy<Value = Vec<u8>> {
+        prop::collection::vec(any::<u8>(), 0..32)
+    }
+
+    fn scalar() -> impl Strategy<Value = Resp> {
+        prop_oneof![
+            line().prop_map(Resp::SimpleString),
+            any::<i64>().prop_map(Resp::Integer),
+            line().prop_map(Resp::Error),
+            prop::option::of(blob()).prop_map(Resp::BulkString),
+            Just(Resp::Array(None)),
+            prop_oneof![any::<f64>(), Just(f64::INFINITY), Just(f64::NEG_INFINITY),]
+                .prop_map(Resp::Double),
+            any::<bool>().prop_map(Resp::Boolean),
+            Just(Resp::Null),
+            (
+                prop::option::of(Just(b'-')),
+                prop::collection::vec(b'0'..=b'9', 1..48)
+            )
+                .prop_map(|(sign, digits)| Resp::BigNumber(
+                    sign.into_iter().chain(digits).collect()
+                )),
+            (any::<[u8; 3]>(), blob())
+                .prop_map(|(format, data)| Resp::VerbatimString { format, data }),
+            blob().prop_map(Resp::BulkError),
+        ]
+    }
+
+    /// Arbitrary values, nested at most 4 levels deep with at most 8 elements per aggregate.
+    fn value() -> impl Strategy<Value = Resp> {
+        scalar().prop_recursive(4, 64, 8, |inner| {
+            let entries = prop::collection::vec((inner.clone(), inner.clone()), 0..8);
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..8)
+                    .prop_map(|values| Resp::Array(Some(values))),
+                entries.clone().prop_map(Resp::Map),
+                prop::collection::vec(inner.clone(), 0..8).prop_map(Resp::Set),
+                prop::collection::vec(inner.clone(), 0..8).prop_map(Resp::Push),
+                (entries, inner).prop_map(|(attributes, value)| Resp::Attribute {
+                    attributes,
+                    value: Box::new(value)
+                }),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips_arbitrary_values(value in value()) {
+            prop_assert_eq!(value.validate(), Ok(()));
+            let encoded = value.encode();
+            prop_assert_eq!(value.encoded_len(), encoded.len());
+            let (rest, parsed) = resp(&encoded).unwrap();
+            prop_assert!(rest.is_empty());
+            prop_assert_eq!(parsed, value);
+        }
+    }
+
+    fn assert_round_trip(input: &[u8]) {
+        let (rest, parsed) = resp(input).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(parsed.encode(), input.to_vec());
+        assert_eq!(parsed.encoded_len(), input.len());
+    }
+
+    #[test]
+    fn encodes_scalars() {
+        assert_round_trip(b"+OK\r\n");
+        assert_round_trip(b":-12345\r\n");
+        assert_round_trip(b":0\r\n");
+        assert_round_trip(b":-9223372036854775808\r\n");
+        assert_round_trip(b"-ERR unknown command\r\n");
+        assert_round_trip(b"$4\r\ngood\r\n");
+        assert_round_trip(b"$0\r\n\r\n");
+        assert_round_trip(b"$-1\r\n");
+        assert_round_trip(b",2.5\r\n");
+        assert_round_trip(b",inf\r\n");
+        assert_round_trip(b",-inf\r\n");
+        assert_round_trip(b",nan\r\n");
+        assert_round_trip(b"#t\r\n");
+        assert_round_trip(b"#f\r\n");
+        assert_round_trip(b"_\r\n");
+        assert_round_trip(b"(-3492890328409238509324850943850943825024385\r\n");
+        assert_round_trip(b"=15\r\ntxt:Some string\r\n");
+        assert_round_trip(b"!21\r\nSYNTAX invalid syntax\r\n");
+    }
+
+    #[test]
+    fn encodes_aggregates() {
+        assert_round_trip(b"*2\r\n$2\r\nOK\r\n*1\r\n:1\r\n");
+        assert_round_trip(b"*0\r\n");
+        assert_round_trip(b"*-1\r\n");
+        assert_round_trip(b"%2\r\n+a\r\n:1\r\n+b\r\n*1\r\n:2\r\n");
+        assert_round_trip(b"~2\r\n+a\r\n+a\r\n");
+        assert_round_trip(b">2\r\n$7\r\nmessage\r\n$5\r\nhello\r\n");
+        assert_round_trip(b"|1\r\n+ttl\r\n:3600\r\n*1\r\n+OK\r\n");
+    }
+
+    #[test]
+    fn encodes_integer_looking_double() {
+        assert_eq!(Resp::Double(10.0).encode(), b",10\r\n".to_vec());
+        assert_eq!(Resp::Double(10.0).encoded_len(), 5);
+    }
+
+    #[test]
+    fn appends_to_reused_buffer() {
+        let mut buf = b"+OK\r\n".to_vec();
+        let value = Resp::Array(Some(vec![Resp::Null, Resp::BulkString(None)]));
+        value.encode_into(&mut buf);
+        assert_eq!(buf, b"+OK\r\n*2\r\n_\r\n$-1\r\n".to_vec());
+
+        buf.clear();
+        value.encode_into(&mut buf);
+        assert_eq!(buf, value.encode());
+    }
+
+    #[test]
+    fn encodes_arrays_element_by_element() {
+        let values = vec![Resp::BulkString(Some(b"a".to_vec())), Resp::Array(None)];
+        let mut buf = Vec::new();
+        let mut encoder = ArrayEncoder::new(values.len(), &mut buf);
+        for value in &values {
+            encoder.encode(value, &mut buf);
+        }
+        assert_eq!(encoder.remaining(), 0);
+        encoder.finish();
+        assert_eq!(buf, Resp::Array(Some(values)).encode());
+
+        assert_eq!(encode_array_header(0), b"*0\r\n".to_vec());
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_too_many_elements() {
+        let mut buf = Vec::new();
+        let mut encoder = ArrayEncoder::new(1, &mut buf);
+        encoder.encode(&Resp::Null, &mut buf);
+        encoder.encode(&Resp::Null, &mut buf);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_too_few_elements() {
+        let mut buf = Vec::new();
+        let mut encoder = ArrayEncoder::new(2, &mut buf);
+        encoder.encode(&Resp::Null, &mut buf);
+        encoder.finish();
+    }
+
+    #[test]
+    fn validates_values_built_by_hand() {
+        let (_, parsed) =
+            resp(&b"*2\r\n%1\r\n+k\r\n(123\r\n|1\r\n-a\r\n:1\r\n$2\r\n\r\n\r\n"[..]).unwrap();
+        assert_eq!(parsed.validate(), Ok(()));
+
+        assert_eq!(
+            Resp::Error(b"ERR\n".to_vec()).validate(),
+            Err(RespError::InvalidLine)
+        );
+        assert_eq!(
+            Resp::BigNumber(b"12a".to_vec()).validate(),
+            Err(RespError::InvalidBigNumber)
+        );
+        let map = Resp::Map(vec![(
+            Resp::BigNumber(vec![]),
+            Resp::SimpleString(b"\r".to_vec()),
+        )]);
+        assert_eq!(map.validate(), Err(RespError::InvalidBigNumber));
+        let attributed = Resp::Attribute {
+            attributes: vec![(Resp::Null, Resp::SimpleString(b"\n".to_vec()))],
+            value: Box::new(Resp::BigNumber(vec![])),
+        };
+        assert_eq!(attributed.validate(), Err(RespError::InvalidLine));
+    }
+
+    #[test]
+    fn validates_deep_nesting_without_recursion() {
+        let mut value = Resp::SimpleString(b"\r\n".to_vec());
+        for _ in 0..100_000 {
+            value = Resp::Array(Some(vec![value]));
+        }
+        assert_eq!(value.validate(), Err(RespError::InvalidLine));
+        // Dropping the value recurses, so take it apart iteratively.
+        while let Resp::Array(Some(mut values)) = value {
+            value = values.pop().unwrap();
+        }
+    }
+
+    #[test]
+    fn encodes_to_writer() {
+        let input = &b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n:12\r\n"[..];
+        let (_, parsed) = resp(input).unwrap();
+        let mut out = std::io::BufWriter::new(Vec::new());
+        parsed.encode_to(&mut out).unwrap();
+        assert_eq!(out.into_inner().unwrap(), parsed.encode());
+    }
+}
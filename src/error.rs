@@ -0,0 +1,124 @@
+use core::{error, fmt};
+
+use nom::error::{ErrorKind, FromExternalError, ParseError};
+
+/// Error explaining why input is not valid RESP, used as nom's error type by all parsers.
+///
+/// # Examples
+/// ```
+/// use respirator::{resp, RespError};
+///
+/// assert_eq!(resp(&b"?\r\n"[..]), Err(nom::Err::Error(RespError::UnknownType(b'?'))));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RespError {
+    /// First byte of the value is not a known RESP type.
+    UnknownType(u8),
+    /// Length or count prefix is not a valid decimal, or is negative where not allowed.
+    InvalidLength,
+    /// Integer payload is not a valid signed decimal.
+    InvalidInteger,
+    /// Integer payload is a valid signed decimal, but does not fit into i64.
+    IntegerOverflow,
+    /// Double payload is not a valid floating point number.
+    InvalidDouble,
+    /// Boolean payload is neither `t` nor `f`.
+    InvalidBoolean,
+    /// Big Number payload is not a valid signed decimal.
+    InvalidBigNumber,
+    /// Simple String or Error payload is not valid UTF-8, reported only in strict mode.
+    InvalidUtf8,
+    /// Verbatim String payload does not start with three bytes format followed by a colon.
+    InvalidVerbatimString,
+    /// Simple String or Error payload contains CR or LF, so it can't be encoded as a line.
+    InvalidLine,
+    /// Chunk of a streamed string does not start with `;`.
+    InvalidChunk,
+    /// Line or payload is not terminated with CRLF.
+    MissingCrlf,
+    /// Input ended before a complete value.
+    UnexpectedEof,
+    /// Aggregates are nested deeper than allowed by the limits.
+    DepthExceeded,
+    /// Declared length or count is larger than allowed by the limits.
+    LimitExceeded,
+    /// Input contains bytes following a complete value, while exactly one was expected.
+    TrailingBytes,
+    /// Value is valid RESP, but not a valid client command.
+    InvalidCommand,
+    /// Any other error reported by nom.
+    Nom(ErrorKind),
+}
+
+impl fmt::Display for RespError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RespError::UnknownType(byte) => write!(f, "unknown type byte: {:?}", *byte as char),
+            RespError::InvalidLength => write!(f, "invalid length"),
+            RespError::InvalidInteger => write!(f, "invalid integer"),
+            RespError::IntegerOverflow => write!(f, "integer overflow"),
+            RespError::InvalidDouble => write!(f, "invalid double"),
+            RespError::InvalidBoolean => write!(f, "invalid boolean"),
+            RespError::InvalidBigNumber => write!(f, "invalid big number"),
+            RespError::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            RespError::InvalidVerbatimString => write!(f, "invalid verbatim string format"),
+            RespError::InvalidLine => write!(f, "line contains CR or LF"),
+            RespError::InvalidChunk => write!(f, "invalid streamed string chunk"),
+            RespError::MissingCrlf => write!(f, "missing CRLF"),
+            RespError::UnexpectedEof => write!(f, "unexpected end of input"),
+            RespError::DepthExceeded => write!(f, "maximum nesting depth exceeded"),
+            RespError::LimitExceeded => write!(f, "maximum length exceeded"),
+            RespError::TrailingBytes => write!(f, "trailing bytes after value"),
+            RespError::InvalidCommand => write!(f, "invalid command"),
+            RespError::Nom(kind) => write!(f, "parsing failed: {}", kind.description()),
+        }
+    }
+}
+
+impl error::Error for RespError {}
+
+/// [`RespError`] together with the position in the input it was found at, returned by [`resp_located`](crate::resp_located).
+///
+/// # Examples
+/// ```
+/// use respirator::{resp_located, LocatedError, RespError};
+///
+/// let error = resp_located(&b"*2\r\n:1\r\n:x\r\n"[..]).unwrap_err();
+/// assert_eq!(error, LocatedError { error: RespError::InvalidInteger, offset: 8 });
+/// assert_eq!(error.to_string(), "invalid integer at offset 8");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LocatedError {
+    /// Why parsing failed.
+    pub error: RespError,
+    /// Offset into the input of the innermost value which failed to parse, i.e. of its type byte.
+    pub offset: usize,
+}
+
+impl fmt::Display for LocatedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.error, self.offset)
+    }
+}
+
+impl error::Error for LocatedError {}
+
+impl<I> ParseError<I> for RespError {
+    fn from_error_kind(_input: I, kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::CrLf => RespError::MissingCrlf,
+            ErrorKind::Eof => RespError::UnexpectedEof,
+            kind => RespError::Nom(kind),
+        }
+    }
+
+    fn append(_input: I, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<I, E> FromExternalError<I, E> for RespError {
+    fn from_external_error(input: I, kind: ErrorKind, _error: E) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
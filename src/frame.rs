@@ -0,0 +1,77 @@
+use nom::IResult;
+
+use crate::{resp, Resp, RespError};
+
+/// Role of a top-level value received on a RESP3 connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Frame {
+    /// Reply to a command sent by the client.
+    Reply,
+    /// Out-of-band `Push`, e.g. a Pub/Sub message or a client-side caching invalidation,
+    /// which doesn't answer any command.
+    Push,
+}
+
+impl Frame {
+    /// Determines role of a top-level value. `Push` carrying attributes is still a push frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the top-level value
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::{Frame, Resp};
+    ///
+    /// assert_eq!(Frame::of(&Resp::Push(vec![])), Frame::Push);
+    /// assert_eq!(Frame::of(&Resp::Integer(1)), Frame::Reply);
+    /// ```
+    pub fn of(value: &Resp) -> Self {
+        match value {
+            Resp::Push(_) => Frame::Push,
+            Resp::Attribute { value, .. } => Frame::of(value),
+            _ => Frame::Reply,
+        }
+    }
+}
+
+/// Parses single RESP value, telling apart pushes from replies, so that pushes can be routed to subscribers
+/// while replies are matched with pending commands.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::{classify, Frame};
+///
+/// let input = &b">2\r\n+invalidate\r\n*1\r\n$3\r\nkey\r\n+OK\r\n"[..];
+/// let (input, (_, frame)) = classify(input).unwrap();
+/// assert_eq!(frame, Frame::Push);
+/// let (_, (_, frame)) = classify(input).unwrap();
+/// assert_eq!(frame, Frame::Reply);
+/// ```
+pub fn classify(input: &[u8]) -> IResult<&[u8], (Resp, Frame), RespError> {
+    let (rest, value) = resp(input)?;
+    let frame = Frame::of(&value);
+    Ok((rest, (value, frame)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_pushes_and_replies() {
+        let input = &b"|1\r\n+ttl\r\n:3\r\n>1\r\n+message\r\n*1\r\n>0\r\n-ERR\r\n"[..];
+        let (input, (_, frame)) = classify(input).unwrap();
+        assert_eq!(frame, Frame::Push);
+        let (input, (_, frame)) = classify(input).unwrap();
+        assert_eq!(frame, Frame::Reply);
+        let (input, (value, frame)) = classify(input).unwrap();
+        assert_eq!(frame, Frame::Reply);
+        assert!(value.is_error());
+        assert!(input.is_empty());
+    }
+}
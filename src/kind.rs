@@ -0,0 +1,155 @@
+use crate::Resp;
+
+/// Type of a RESP value, i.e. variant of [`Resp`] without its payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RespType {
+    /// Simple String (`+`).
+    SimpleString,
+    /// Integer (`:`).
+    Integer,
+    /// Error (`-`).
+    Error,
+    /// Bulk String (`$`).
+    BulkString,
+    /// Array (`*`).
+    Array,
+    /// Double in RESP3 (`,`).
+    Double,
+    /// Boolean in RESP3 (`#`).
+    Boolean,
+    /// Null in RESP3 (`_`).
+    Null,
+    /// Map in RESP3 (`%`).
+    Map,
+    /// Set in RESP3 (`~`).
+    Set,
+    /// Push in RESP3 (`>`).
+    Push,
+    /// Big Number in RESP3 (`(`).
+    BigNumber,
+    /// Verbatim String in RESP3 (`=`).
+    VerbatimString,
+    /// Bulk Error in RESP3 (`!`).
+    BulkError,
+    /// Attribute in RESP3 (`|`).
+    Attribute,
+}
+
+/// Maps type byte, i.e. the first byte of a value, to its type. Returns None for unknown bytes.
+///
+/// # Arguments
+///
+/// * `byte` - the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{type_byte, RespType};
+///
+/// assert_eq!(type_byte(b'$'), Some(RespType::BulkString));
+/// assert_eq!(type_byte(b'?'), None);
+/// ```
+pub fn type_byte(byte: u8) -> Option<RespType> {
+    match byte {
+        b'+' => Some(RespType::SimpleString),
+        b':' => Some(RespType::Integer),
+        b'-' => Some(RespType::Error),
+        b'$' => Some(RespType::BulkString),
+        b'*' => Some(RespType::Array),
+        b',' => Some(RespType::Double),
+        b'#' => Some(RespType::Boolean),
+        b'_' => Some(RespType::Null),
+        b'%' => Some(RespType::Map),
+        b'~' => Some(RespType::Set),
+        b'>' => Some(RespType::Push),
+        b'(' => Some(RespType::BigNumber),
+        b'=' => Some(RespType::VerbatimString),
+        b'!' => Some(RespType::BulkError),
+        b'|' => Some(RespType::Attribute),
+        _ => None,
+    }
+}
+
+impl Resp {
+    /// Returns type of the value.
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::{resp, RespType};
+    ///
+    /// let (_, parsed) = resp(&b"*-1\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.resp_type(), RespType::Array);
+    /// ```
+    pub fn resp_type(&self) -> RespType {
+        match self {
+            Resp::SimpleString(_) => RespType::SimpleString,
+            Resp::Integer(_) => RespType::Integer,
+            Resp::Error(_) => RespType::Error,
+            Resp::BulkString(_) => RespType::BulkString,
+            Resp::Array(_) => RespType::Array,
+            Resp::Double(_) => RespType::Double,
+            Resp::Boolean(_) => RespType::Boolean,
+            Resp::Null => RespType::Null,
+            Resp::Map(_) => RespType::Map,
+            Resp::Set(_) => RespType::Set,
+            Resp::Push(_) => RespType::Push,
+            Resp::BigNumber(_) => RespType::BigNumber,
+            Resp::VerbatimString { .. } => RespType::VerbatimString,
+            Resp::BulkError(_) => RespType::BulkError,
+            Resp::Attribute { .. } => RespType::Attribute,
+        }
+    }
+
+    /// Checks whether value is of the given type.
+    ///
+    /// # Arguments
+    ///
+    /// * `resp_type` - the expected type
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::{Resp, RespType};
+    ///
+    /// assert!(Resp::Integer(8).matches_type(RespType::Integer));
+    /// assert!(!Resp::Integer(8).matches_type(RespType::BulkString));
+    /// ```
+    pub fn matches_type(&self, resp_type: RespType) -> bool {
+        self.resp_type() == resp_type
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resp;
+
+    #[test]
+    fn maps_type_bytes_of_encoded_values() {
+        for input in [
+            &b"+OK\r\n"[..],
+            &b":1\r\n"[..],
+            &b"-ERR\r\n"[..],
+            &b"$-1\r\n"[..],
+            &b"*0\r\n"[..],
+            &b",2.5\r\n"[..],
+            &b"#f\r\n"[..],
+            &b"_\r\n"[..],
+            &b"%0\r\n"[..],
+            &b"~0\r\n"[..],
+            &b">0\r\n"[..],
+            &b"(1\r\n"[..],
+            &b"=4\r\ntxt:\r\n"[..],
+            &b"!3\r\nERR\r\n"[..],
+            &b"|0\r\n_\r\n"[..],
+        ] {
+            let (_, parsed) = resp(input).unwrap();
+            assert_eq!(type_byte(input[0]), Some(parsed.resp_type()));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_type_bytes() {
+        for byte in [b'?', b'\r', b'a', 0] {
+            assert_eq!(type_byte(byte), None);
+        }
+    }
+}
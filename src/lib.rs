@@ -1,5 +1,5 @@
 //! # Respirator - cleanly inhale RESP stream.
-//! Respirator is [nom](https://github.com/Geal/nom) based [Redis Serialization Protocol (resp)](https://redis.io/topics/protocol) parser. Currently only "complete" parsing (i.e. works only when all data to parse is available) works, yet an aim is to cover streaming parsing as well.
+//! Respirator is [nom](https://github.com/Geal/nom) based [Redis Serialization Protocol (resp)](https://redis.io/topics/protocol) parser. Both "complete" parsing (i.e. works only when all data to parse is available) with `resp` and streaming parsing (i.e. reports `Incomplete` when more data is needed) with `resp_streaming` are supported.
 //!
 //! ## Usage
 //! ### Example
@@ -16,6 +16,56 @@
 //!   assert!(matches!(Resp::BulkString(Some(b"Resp".to_vec())), bulk_string));
 //! }
 //! ```
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "alloc"))]
+compile_error!("respirator requires the `alloc` feature");
+
+extern crate alloc;
+
+#[cfg(feature = "tokio")]
+pub mod async_io;
+pub mod borrowed;
+pub mod cluster;
+pub mod command;
+pub mod config;
+pub mod convert;
+mod display;
+mod encode;
+pub mod error;
+pub mod frame;
+pub mod kind;
+mod macros;
 pub mod parser;
+pub mod stream;
+pub mod types;
+mod value;
+pub mod visit;
+
+#[cfg(feature = "tokio")]
+pub use async_io::{AsyncRespReader, RespCodec};
+pub use borrowed::RespRef;
+pub use cluster::{Redirect, RedirectKind};
+pub use command::{
+    inline_command, inline_command_with_config, parse_command, parse_command_with_config, request,
+    request_streaming_with_config, request_with_config, Command, CommandArg, CommandBuilder,
+};
+pub use config::{IntegerOverflow, ParseConfig};
+pub use convert::ConversionError;
+pub use encode::{encode_array_header, ArrayEncoder};
+pub use error::{LocatedError, RespError};
+pub use frame::{classify, Frame};
+pub use kind::{type_byte, RespType};
+pub use parser::{
+    decode_frames, parse_all, parse_exact, parse_n, resp, resp_located, resp_ref, resp_streaming,
+    resp_with_bulk, resp_with_config, resp_with_limits, resp_with_raw, try_frame_len, Resp,
+};
+pub use stream::Parser;
+#[cfg(feature = "std")]
+pub use stream::{from_bufread, from_bufread_with_config, RespReader};
+pub use visit::{parse_events, RespVisitor};
 
-pub use parser::{resp, Resp};
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec;
+}
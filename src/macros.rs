@@ -0,0 +1,104 @@
+/// Builds [`Resp`](crate::Resp) value with a concise syntax, checked at compile time like any other expression.
+///
+/// * `null`, `null_bulk` and `null_array` build RESP3 Null, null Bulk String and null Array;
+/// * `int`, `double` and `bool` followed by an expression build Integer, Double and Boolean;
+/// * `simple`, `error` and `bulk` followed by a [`CommandArg`](crate::CommandArg) (e.g. `&str`, bytes or a number)
+///   build Simple String, Error and Bulk String;
+/// * `array`, `set` and `push` followed by `[...]` build aggregates of the listed elements,
+///   `map` followed by `{key => value, ...}` builds Map;
+/// * any other expression is converted with `Resp::from`, so elements of aggregates may be e.g. `&str` for
+///   Bulk Strings, integers or nested `resp!` invocations.
+///
+/// # Examples
+/// ```
+/// use respirator::{resp, Resp};
+///
+/// assert_eq!(resp!(int 42), Resp::Integer(42));
+/// assert_eq!(resp!(array["GET", "key"]).encode(), b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n".to_vec());
+/// assert_eq!(
+///     resp!(map { "proto" => 3, "modules" => resp!(array[]) }),
+///     Resp::Map(vec![
+///         (Resp::BulkString(Some(b"proto".to_vec())), Resp::Integer(3)),
+///         (Resp::BulkString(Some(b"modules".to_vec())), Resp::Array(Some(vec![]))),
+///     ])
+/// );
+/// ```
+#[macro_export]
+macro_rules! resp {
+    (null) => {
+        $crate::Resp::Null
+    };
+    (null_bulk) => {
+        $crate::Resp::BulkString(::core::option::Option::None)
+    };
+    (null_array) => {
+        $crate::Resp::Array(::core::option::Option::None)
+    };
+    (int $val:expr) => {
+        $crate::Resp::Integer($val)
+    };
+    (double $val:expr) => {
+        $crate::Resp::Double($val)
+    };
+    (bool $val:expr) => {
+        $crate::Resp::Boolean($val)
+    };
+    (simple $val:expr) => {
+        $crate::Resp::SimpleString($crate::CommandArg::into_arg($val))
+    };
+    (error $val:expr) => {
+        $crate::Resp::Error($crate::CommandArg::into_arg($val))
+    };
+    (bulk $val:expr) => {
+        $crate::Resp::BulkString(::core::option::Option::Some($crate::CommandArg::into_arg($val)))
+    };
+    (array [$($elem:expr),* $(,)?]) => {
+        $crate::Resp::Array(::core::option::Option::Some($crate::__private::vec![$($crate::Resp::from($elem)),*]))
+    };
+    (set [$($elem:expr),* $(,)?]) => {
+        $crate::Resp::Set($crate::__private::vec![$($crate::Resp::from($elem)),*])
+    };
+    (push [$($elem:expr),* $(,)?]) => {
+        $crate::Resp::Push($crate::__private::vec![$($crate::Resp::from($elem)),*])
+    };
+    (map {$($key:expr => $value:expr),* $(,)?}) => {
+        $crate::Resp::Map($crate::__private::vec![$(($crate::Resp::from($key), $crate::Resp::from($value))),*])
+    };
+    ($val:expr) => {
+        $crate::Resp::from($val)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Resp;
+
+    #[test]
+    fn builds_scalars() {
+        assert_eq!(resp!(null), Resp::Null);
+        assert_eq!(resp!(null_bulk), Resp::BulkString(None));
+        assert_eq!(resp!(null_array), Resp::Array(None));
+        assert_eq!(resp!(int - 8), Resp::Integer(-8));
+        assert_eq!(resp!(double 2.5), Resp::Double(2.5));
+        assert_eq!(resp!(bool true), Resp::Boolean(true));
+        assert_eq!(resp!(simple "OK"), Resp::SimpleString(b"OK".to_vec()));
+        assert_eq!(resp!(error "ERR"), Resp::Error(b"ERR".to_vec()));
+        assert_eq!(resp!(bulk 12), Resp::BulkString(Some(b"12".to_vec())));
+        assert_eq!(resp!("key"), Resp::BulkString(Some(b"key".to_vec())));
+    }
+
+    #[test]
+    fn builds_nested_aggregates() {
+        let built = resp!(array[
+            "SUBSCRIBE",
+            resp!(set[1, resp!(null)]),
+            resp!(push[]),
+            resp!(map { resp!(simple "k") => resp!(array["v",]) }),
+        ]);
+        let (_, parsed) = crate::resp(
+            &b"*4\r\n$9\r\nSUBSCRIBE\r\n~2\r\n:1\r\n_\r\n>0\r\n%1\r\n+k\r\n*1\r\n$1\r\nv\r\n"[..],
+        )
+        .unwrap();
+        assert_eq!(built, parsed);
+    }
+}
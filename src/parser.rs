@@ -1,14 +1,19 @@
-use nom::{
-    bytes::complete::take,
-    character::complete::{crlf, not_line_ending},
-    multi::count,
-    sequence::terminated,
-    IResult,
-};
+use alloc::{boxed::Box, vec::Vec};
 
+use nom::{bytes, character, Err, IResult, Needed};
+
+use crate::{IntegerOverflow, LocatedError, ParseConfig, RespError, RespRef};
 
 /// Enum for types defined in RESP specification.
 /// Its variants contain Vec<u8> or Option<Vec<u8>> for optional types (i.e. Bulk Strings and Arrays).
+///
+/// `Resp` implements `Eq` and `Hash`, so it can be used as a key of hashed collections.
+/// Unlike `f64`, `Double` holding `NaN` is equal to itself, so such values can be looked up too.
+///
+/// With `serde` feature enabled `Resp` implements `Serialize` and `Deserialize`, using externally tagged
+/// representation (e.g. `{"Integer":8}`) with byte payloads as arrays of numbers.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Resp {
     /// Simple string in RESP.
     ///
@@ -40,7 +45,7 @@ pub enum Resp {
     /// }
     /// ```
     Error(Vec<u8>),
-    /// Bulk String in RESP, contains None if encounters empty string.
+    /// Bulk String in RESP, contains None if encounters null bulk string (`$-1`).
     ///
     /// # Examples
     /// ```
@@ -52,26 +57,139 @@ pub enum Resp {
     ///
     /// use std::matches;
     /// /// Empty Bulk String
-    /// let empty_bulk_string = respirator::resp(&b"$0\r\n"[..]);
-    /// assert!(matches!(respirator::Resp::BulkString(None), empty_bulk_string));
+    /// let empty_bulk_string = respirator::resp(&b"$0\r\n\r\n"[..]);
+    /// assert!(matches!(empty_bulk_string, Ok((_, respirator::Resp::BulkString(Some(value)))) if value.is_empty()));
+    ///
+    /// /// Null Bulk String
+    /// let null_bulk_string = respirator::resp(&b"$-1\r\n"[..]);
+    /// assert!(matches!(null_bulk_string, Ok((_, respirator::Resp::BulkString(None)))));
     /// ```
     BulkString(Option<Vec<u8>>),
-    /// Array in RESP, contains None if encounters empty array.
+    /// Array in RESP, contains None if encounters null array (`*-1`).
     ///
     /// # Examples
     /// ```
-    /// /// Bulk String
-    /// let bulk_string = respirator::resp(&b"$3\r\nstr\r\n"[..]);
-    /// if let (_, respirator::Resp::BulkString(Some(value))) = bulk_string.unwrap() {
-    ///   assert_eq!(value, b"str".to_vec());
+    /// /// Array
+    /// let array = respirator::resp(&b"*1\r\n:8\r\n"[..]);
+    /// if let (_, respirator::Resp::Array(Some(values))) = array.unwrap() {
+    ///   assert_eq!(values.len(), 1);
     /// }
     ///
     /// use std::matches;
-    /// /// Empty Bulk String
-    /// let empty_bulk_string = respirator::resp(&b"$0\r\n"[..]);
-    /// assert!(matches!(respirator::Resp::BulkString(None), empty_bulk_string));
+    /// /// Empty Array
+    /// let empty_array = respirator::resp(&b"*0\r\n"[..]);
+    /// assert!(matches!(empty_array, Ok((_, respirator::Resp::Array(Some(values)))) if values.is_empty()));
+    ///
+    /// /// Null Array
+    /// let null_array = respirator::resp(&b"*-1\r\n"[..]);
+    /// assert!(matches!(null_array, Ok((_, respirator::Resp::Array(None)))));
     /// ```
     Array(Option<Vec<Resp>>),
+    /// Double in RESP3. Contains f64 value, `inf`, `-inf` and `nan` are mapped to their f64 counterparts.
+    ///
+    /// # Examples
+    /// ```
+    /// let double = respirator::resp(&b",2.5\r\n"[..]);
+    /// if let (_, respirator::Resp::Double(value)) = double.unwrap() {
+    ///   assert_eq!(value, 2.5);
+    /// }
+    /// ```
+    Double(f64),
+    /// Boolean in RESP3. Contains true for `#t` and false for `#f`.
+    ///
+    /// # Examples
+    /// ```
+    /// let boolean = respirator::resp(&b"#t\r\n"[..]);
+    /// if let (_, respirator::Resp::Boolean(value)) = boolean.unwrap() {
+    ///   assert!(value);
+    /// }
+    /// ```
+    Boolean(bool),
+    /// Null in RESP3, distinct from null Bulk Strings and Arrays of RESP2.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::matches;
+    /// let null = respirator::resp(&b"_\r\n"[..]);
+    /// assert!(matches!(null.unwrap(), (_, respirator::Resp::Null)));
+    /// ```
+    Null,
+    /// Map in RESP3, contains key-value pairs in the order they were sent.
+    ///
+    /// # Examples
+    /// ```
+    /// let map = respirator::resp(&b"%1\r\n+key\r\n:1\r\n"[..]);
+    /// if let (_, respirator::Resp::Map(entries)) = map.unwrap() {
+    ///   assert_eq!(entries.len(), 1);
+    /// }
+    /// ```
+    Map(Vec<(Resp, Resp)>),
+    /// Set in RESP3, elements are kept in the order they were sent and are not deduplicated.
+    ///
+    /// # Examples
+    /// ```
+    /// let set = respirator::resp(&b"~2\r\n+a\r\n+b\r\n"[..]);
+    /// if let (_, respirator::Resp::Set(values)) = set.unwrap() {
+    ///   assert_eq!(values.len(), 2);
+    /// }
+    /// ```
+    Set(Vec<Resp>),
+    /// Push in RESP3, structurally an array but sent out-of-band by the server (e.g. pub/sub messages).
+    ///
+    /// # Examples
+    /// ```
+    /// let push = respirator::resp(&b">2\r\n$7\r\nmessage\r\n$5\r\nhello\r\n"[..]);
+    /// if let (_, respirator::Resp::Push(values)) = push.unwrap() {
+    ///   assert_eq!(values.len(), 2);
+    /// }
+    /// ```
+    Push(Vec<Resp>),
+    /// Big Number in RESP3. Contains raw, optionally signed, decimal digits.
+    ///
+    /// # Examples
+    /// ```
+    /// let big_number = respirator::resp(&b"(3492890328409238509324850943850943825024385\r\n"[..]);
+    /// if let (_, respirator::Resp::BigNumber(value)) = big_number.unwrap() {
+    ///   assert_eq!(value, b"3492890328409238509324850943850943825024385".to_vec());
+    /// }
+    /// ```
+    BigNumber(Vec<u8>),
+    /// Verbatim String in RESP3. Contains three bytes format hint (e.g. `txt` or `mkd`) and the data following it.
+    ///
+    /// # Examples
+    /// ```
+    /// let verbatim_string = respirator::resp(&b"=15\r\ntxt:Some string\r\n"[..]);
+    /// if let (_, respirator::Resp::VerbatimString { format, data }) = verbatim_string.unwrap() {
+    ///   assert_eq!(&format, b"txt");
+    ///   assert_eq!(data, b"Some string".to_vec());
+    /// }
+    /// ```
+    VerbatimString { format: [u8; 3], data: Vec<u8> },
+    /// Bulk Error in RESP3, length prefixed counterpart of Error.
+    ///
+    /// # Examples
+    /// ```
+    /// let bulk_error = respirator::resp(&b"!21\r\nSYNTAX invalid syntax\r\n"[..]);
+    /// if let (_, respirator::Resp::BulkError(value)) = bulk_error.unwrap() {
+    ///   assert_eq!(value, b"SYNTAX invalid syntax".to_vec());
+    /// }
+    /// ```
+    BulkError(Vec<u8>),
+    /// Attribute in RESP3, out-of-band metadata (e.g. key popularity) preceding the actual reply.
+    /// Callers not interested in metadata can simply use `value`.
+    ///
+    /// # Examples
+    /// ```
+    /// let attribute = respirator::resp(&b"|1\r\n+ttl\r\n:3600\r\n+OK\r\n"[..]);
+    /// if let (_, respirator::Resp::Attribute { attributes, value }) = attribute.unwrap() {
+    ///   assert_eq!(attributes.len(), 1);
+    ///   assert_eq!(*value, respirator::Resp::SimpleString(b"OK".to_vec()));
+    /// }
+    /// ```
+    Attribute {
+        attributes: Vec<(Resp, Resp)>,
+        value: Box<Resp>,
+    },
 }
 
 /// Main function for RESP parsing, conforming nom's contract.
@@ -94,163 +212,2332 @@ pub enum Resp {
 ///   assert!(matches!(Resp::BulkString(Some(b"Resp".to_vec())), bulk_string));
 /// }
 /// ```
-pub fn resp(input: &[u8]) -> IResult<&[u8], Resp> {
-    let (input, val) = take(1usize)(input)?;
-    match val[0] {
-        b'+' => simple_string(input),
-        b':' => integer(input),
-        b'-' => error(input),
-        b'$' => bulk_string(input),
-        b'*' => array(input),
-        _ => panic!("Unknown type byte: {:?}", val),
-    }
+pub fn resp(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    value(input, Context::new(false, ParseConfig::default()))
 }
 
-fn simple_string(input: &[u8]) -> IResult<&[u8], Resp> {
-    let (input, val) = terminated(not_line_ending, crlf)(input)?;
-    Ok((input, Resp::SimpleString(val.to_vec())))
+/// Streaming counterpart of [`resp`], returning `nom::Err::Incomplete` instead of an error
+/// when the input ends before a complete RESP value, so that parsing can be retried once more data arrives.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::{Resp, resp_streaming};
+///
+/// let partial = &b"$4\r\nRe"[..];
+/// assert!(matches!(resp_streaming(partial), Err(nom::Err::Incomplete(_))));
+///
+/// let complete = &b"$4\r\nResp\r\n"[..];
+/// let (_, parsed) = resp_streaming(complete).unwrap();
+/// assert_eq!(parsed, Resp::BulkString(Some(b"Resp".to_vec())));
+/// ```
+pub fn resp_streaming(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    value(input, Context::new(true, ParseConfig::default()))
 }
 
-fn integer(input: &[u8]) -> IResult<&[u8], Resp> {
-    let (input, val) = terminated(not_line_ending, crlf)(input)?;
-    Ok((
-        input,
-        Resp::Integer(String::from_utf8_lossy(val).parse::<i64>().unwrap()),
-    ))
+/// Borrowing counterpart of [`resp`], returning [`RespRef`] which refers to the input instead of copying its payloads.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::{resp_ref, RespRef};
+///
+/// let input = &b"*2\r\n$2\r\nOK\r\n$4\r\nResp\r\n"[..];
+/// let (_, parsed) = resp_ref(input).unwrap();
+/// assert_eq!(
+///     parsed,
+///     RespRef::Array(Some(vec![RespRef::BulkString(Some(b"OK")), RespRef::BulkString(Some(b"Resp"))]))
+/// );
+/// ```
+pub fn resp_ref(input: &[u8]) -> IResult<&[u8], RespRef<'_>, RespError> {
+    value(input, Context::new(false, ParseConfig::default()))
 }
 
-fn error(input: &[u8]) -> IResult<&[u8], Resp> {
-    let (input, val) = terminated(not_line_ending, crlf)(input)?;
-    Ok((input, Resp::Error(val.to_vec())))
+/// Parses single RESP value like [`resp`], also returning the slice of input it was parsed from,
+/// e.g. to forward the value verbatim without encoding it again.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::{resp_with_raw, Resp};
+///
+/// let (rest, (parsed, raw)) = resp_with_raw(&b":+8\r\n+OK\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::Integer(8));
+/// assert_eq!(raw, b":+8\r\n");
+/// assert_eq!(rest, b"+OK\r\n");
+/// ```
+pub fn resp_with_raw(input: &[u8]) -> IResult<&[u8], (Resp, &[u8]), RespError> {
+    let (rest, parsed) = resp(input)?;
+    let raw = &input[..input.len() - rest.len()];
+    Ok((rest, (parsed, raw)))
 }
 
-fn bulk_string(input: &[u8]) -> IResult<&[u8], Resp> {
-    let (input, len) = length(input)?;
-    if len == 0 {
-        return Ok((input, Resp::BulkString(None)));
-    }
-    let (input, val) = terminated(take(len), crlf)(input)?;
+/// Parses single RESP value like [`resp`], but lets the callback provide the storage of every non-null Bulk String payload.
+/// See [`RespRef::to_owned_with`].
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+/// * `bulk` - callback turning borrowed Bulk String payload into an owned one
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use respirator::{resp_with_bulk, Resp};
+///
+/// // Reuse buffers allocated for payloads seen before.
+/// let mut cache: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+/// let input = &b"*2\r\n$3\r\nkey\r\n$3\r\nkey\r\n"[..];
+/// let (_, parsed) = resp_with_bulk(input, |val| {
+///     cache.entry(val.to_vec()).or_insert_with(|| val.to_vec()).clone()
+/// })
+/// .unwrap();
+/// assert_eq!(parsed.as_array().unwrap().len(), 2);
+/// assert_eq!(cache.len(), 1);
+/// ```
+pub fn resp_with_bulk<F: FnMut(&[u8]) -> Vec<u8>>(
+    input: &[u8],
+    mut bulk: F,
+) -> IResult<&[u8], Resp, RespError> {
+    let (rest, parsed) = resp_ref(input)?;
+    Ok((rest, parsed.to_owned_with(&mut bulk)))
+}
 
-    Ok((input, Resp::BulkString(Some(val.to_vec()))))
+/// Parses single RESP value like [`resp`], reporting where in the input parsing failed.
+/// The offset points at the innermost value which is malformed, e.g. at the element rather than at the enclosing array.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::{resp_located, RespError};
+///
+/// let input = &b"*3\r\n+OK\r\n$4\r\nbad\r\n+OK\r\n"[..];
+/// let error = resp_located(input).unwrap_err();
+/// assert_eq!(error.error, RespError::MissingCrlf);
+/// assert_eq!(&input[error.offset..], b"$4\r\nbad\r\n+OK\r\n");
+/// ```
+pub fn resp_located(input: &[u8]) -> Result<(&[u8], Resp), LocatedError> {
+    located(input, Context::new(false, ParseConfig::default())).map_err(|(error, rest)| {
+        let error = match error {
+            Err::Error(error) | Err::Failure(error) => error,
+            Err::Incomplete(_) => RespError::UnexpectedEof,
+        };
+        LocatedError {
+            error,
+            offset: input.len() - rest.len(),
+        }
+    })
 }
 
-fn length(input: &[u8]) -> IResult<&[u8], usize> {
-    let (input, len) = terminated(not_line_ending, crlf)(input)?;
-    Ok((input, String::from_utf8_lossy(len).parse().unwrap()))
+/// Types which RESP values can be built of, with payloads borrowed from the parsed input.
+pub(crate) trait FromWire<'a>: Sized {
+    fn simple_string(val: &'a [u8]) -> Self;
+    fn integer(val: i64) -> Self;
+    fn error(val: &'a [u8]) -> Self;
+    fn bulk_string(val: Option<&'a [u8]>) -> Self;
+    fn streamed_string(chunks: Vec<&'a [u8]>) -> Self;
+    fn array(values: Option<Vec<Self>>) -> Self;
+    fn double(val: f64) -> Self;
+    fn boolean(val: bool) -> Self;
+    fn null() -> Self;
+    fn map(entries: Vec<(Self, Self)>) -> Self;
+    fn set(values: Vec<Self>) -> Self;
+    fn push(values: Vec<Self>) -> Self;
+    fn big_number(val: &'a [u8]) -> Self;
+    fn verbatim_string(format: [u8; 3], data: &'a [u8]) -> Self;
+    fn bulk_error(val: &'a [u8]) -> Self;
+    fn attribute(attributes: Vec<(Self, Self)>, value: Self) -> Self;
 }
 
-fn array(input: &[u8]) -> IResult<&[u8], Resp> {
-    let (input, len) = length(input)?;
-    if len == 0 {
-        return Ok((input, Resp::Array(None)));
+impl<'a> FromWire<'a> for Resp {
+    fn simple_string(val: &'a [u8]) -> Self {
+        Resp::SimpleString(val.to_vec())
     }
-    let (input, res) = count(resp, len)(input)?;
-    Ok((input, Resp::Array(Some(res))))
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn integer(val: i64) -> Self {
+        Resp::Integer(val)
+    }
 
-    #[test]
-    fn parses_simple_strings() {
-        let input = &b"+OK - seems good.\r\n"[..];
-        let parsed = resp(input).unwrap();
-        if let Resp::SimpleString(parsed) = parsed.1 {
-            assert_eq!(parsed, b"OK - seems good.".to_vec());
-        } else {
-            panic!("Error parsing SimpleString");
-        }
+    fn error(val: &'a [u8]) -> Self {
+        Resp::Error(val.to_vec())
     }
 
-    #[test]
-    #[should_panic]
-    fn fails_on_corrupted_simple_string() {
-        let corrupted_input = &b"+OK - seems bad.\r"[..];
-        resp(corrupted_input).unwrap();
+    fn bulk_string(val: Option<&'a [u8]>) -> Self {
+        Resp::BulkString(val.map(<[u8]>::to_vec))
     }
 
-    #[test]
-    fn parses_integers() {
-        let input = &b":12345\r\n"[..];
-        let parsed = resp(input).unwrap();
-        if let Resp::Integer(parsed) = parsed.1 {
-            assert_eq!(parsed, 12345);
-        } else {
-            panic!("Error parsing Integer");
-        }
+    fn streamed_string(chunks: Vec<&'a [u8]>) -> Self {
+        Resp::BulkString(Some(chunks.concat()))
     }
 
-    #[test]
-    #[should_panic]
-    fn fails_on_corrupted_integer() {
-        let corrupted_input = &b":OK - seems bad.\r\n"[..];
-        resp(corrupted_input).unwrap();
+    fn array(values: Option<Vec<Self>>) -> Self {
+        Resp::Array(values)
     }
 
-    #[test]
-    fn parses_errors() {
-        let input = &b"-this is an error\r\n"[..];
-        let parsed = resp(input).unwrap();
-        if let Resp::Error(parsed) = parsed.1 {
-            assert_eq!(parsed, b"this is an error".to_vec());
-        } else {
-            panic!("Error parsing Error");
-        }
+    fn double(val: f64) -> Self {
+        Resp::Double(val)
     }
 
-    #[test]
-    #[should_panic]
-    fn fails_on_corrupted_error() {
-        let corrupted_input = &b"-an error - seems bad.\n"[..];
-        resp(corrupted_input).unwrap();
+    fn boolean(val: bool) -> Self {
+        Resp::Boolean(val)
     }
 
-    #[test]
-    fn parses_bulk_string() {
-        let input = &b"$4\r\ngood\r\n"[..];
-        let parsed = resp(input).unwrap();
-        if let Resp::BulkString(Some(parsed)) = parsed.1 {
-            assert_eq!(parsed, b"good".to_vec());
-        } else {
-            panic!("Error parsing BulkString");
-        }
+    fn null() -> Self {
+        Resp::Null
     }
 
-    #[test]
-    #[should_panic]
-    fn fails_on_corrupted_bulk_string() {
-        let corrupted_input = &b"$4\r\nbad\r\n"[..];
-        resp(corrupted_input).unwrap();
+    fn map(entries: Vec<(Self, Self)>) -> Self {
+        Resp::Map(entries)
     }
 
-    #[test]
-    fn parses_array() {
-        let input = &b"*2\r\n$2\r\nOK\r\n$4\r\nResp\r\n"[..];
-        let parsed = resp(input).unwrap();
+    fn set(values: Vec<Self>) -> Self {
+        Resp::Set(values)
+    }
 
-        if let Resp::Array(Some(parsed)) = parsed.1 {
-            if let [Resp::BulkString(Some(str1)), Resp::BulkString(Some(str2))] = &parsed[..] {
-                assert_eq!(*str1, b"OK".to_vec());
-                assert_eq!(*str2, b"Resp".to_vec());
-            } else {
-                panic!("Error parsing Array");
-            }
-        } else {
-            panic!("Error parsing Array");
+    fn push(values: Vec<Self>) -> Self {
+        Resp::Push(values)
+    }
+
+    fn big_number(val: &'a [u8]) -> Self {
+        Resp::BigNumber(val.to_vec())
+    }
+
+    fn verbatim_string(format: [u8; 3], data: &'a [u8]) -> Self {
+        Resp::VerbatimString {
+            format,
+            data: data.to_vec(),
         }
     }
 
-    #[test]
-    fn parses_empty_array() {
-        let input = &b"*0\r\n"[..];
-        let parsed = resp(input).unwrap();
+    fn bulk_error(val: &'a [u8]) -> Self {
+        Resp::BulkError(val.to_vec())
+    }
 
-        if let Resp::Array(None) = parsed.1 {
-        } else {
-            panic!("Error parsing Array");
+    fn attribute(attributes: Vec<(Self, Self)>, value: Self) -> Self {
+        Resp::Attribute {
+            attributes,
+            value: Box::new(value),
         }
     }
+}
+
+/// Placeholder for values which are only validated and measured, never built.
+/// Aggregates collect it into `Vec`s of a zero-sized type, so skipping allocates nothing.
+struct Skip;
+
+impl<'a> FromWire<'a> for Skip {
+    fn simple_string(_: &'a [u8]) -> Self {
+        Skip
+    }
+
+    fn integer(_: i64) -> Self {
+        Skip
+    }
+
+    fn error(_: &'a [u8]) -> Self {
+        Skip
+    }
+
+    fn bulk_string(_: Option<&'a [u8]>) -> Self {
+        Skip
+    }
+
+    fn streamed_string(_: Vec<&'a [u8]>) -> Self {
+        Skip
+    }
+
+    fn array(_: Option<Vec<Self>>) -> Self {
+        Skip
+    }
+
+    fn double(_: f64) -> Self {
+        Skip
+    }
+
+    fn boolean(_: bool) -> Self {
+        Skip
+    }
+
+    fn null() -> Self {
+        Skip
+    }
+
+    fn map(_: Vec<(Self, Self)>) -> Self {
+        Skip
+    }
+
+    fn set(_: Vec<Self>) -> Self {
+        Skip
+    }
+
+    fn push(_: Vec<Self>) -> Self {
+        Skip
+    }
+
+    fn big_number(_: &'a [u8]) -> Self {
+        Skip
+    }
+
+    fn verbatim_string(_: [u8; 3], _: &'a [u8]) -> Self {
+        Skip
+    }
+
+    fn bulk_error(_: &'a [u8]) -> Self {
+        Skip
+    }
+
+    fn attribute(_: Vec<(Self, Self)>, _: Self) -> Self {
+        Skip
+    }
+}
+
+/// Variant of [`resp`] parsing according to the given [`ParseConfig`], e.g. failing with `RespError::DepthExceeded`
+/// or `RespError::LimitExceeded` once any of its limits is exceeded.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+/// * `config` - options of the parser
+///
+/// # Examples
+/// ```
+/// use respirator::{resp_with_config, IntegerOverflow, ParseConfig, Resp};
+///
+/// let config = ParseConfig::new().integer_overflow(IntegerOverflow::Saturate);
+/// let (_, parsed) = resp_with_config(&b":-9223372036854775809\r\n"[..], &config).unwrap();
+/// assert_eq!(parsed, Resp::Integer(i64::MIN));
+/// ```
+pub fn resp_with_config<'a>(
+    input: &'a [u8],
+    config: &ParseConfig,
+) -> IResult<&'a [u8], Resp, RespError> {
+    value(input, Context::new(false, *config))
+}
+
+/// Variant of [`resp`] enforcing limits of the given [`ParseConfig`], same as [`resp_with_config`], failing with `RespError::DepthExceeded`
+/// or `RespError::LimitExceeded` once any of them is exceeded.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+/// * `limits` - limits to enforce
+///
+/// # Examples
+/// ```
+/// use respirator::{resp_with_limits, ParseConfig};
+///
+/// let limits = ParseConfig { max_depth: 2, ..ParseConfig::default() };
+/// let nested = &b"*1\r\n*1\r\n*1\r\n:1\r\n"[..];
+/// assert!(matches!(
+///     resp_with_limits(nested, &limits),
+///     Err(nom::Err::Error(respirator::RespError::DepthExceeded))
+/// ));
+/// ```
+pub fn resp_with_limits<'a>(
+    input: &'a [u8],
+    limits: &ParseConfig,
+) -> IResult<&'a [u8], Resp, RespError> {
+    value(input, Context::new(false, *limits))
+}
+
+/// Parses all RESP values in the input, e.g. a batch of pipelined replies.
+/// Stops at the end of input and fails if any of the values is malformed.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::{parse_all, Resp};
+///
+/// let (_, parsed) = parse_all(&b"+OK\r\n:8\r\n"[..]).unwrap();
+/// assert_eq!(parsed, vec![Resp::SimpleString(b"OK".to_vec()), Resp::Integer(8)]);
+/// ```
+pub fn parse_all(mut input: &[u8]) -> IResult<&[u8], Vec<Resp>, RespError> {
+    let mut values = Vec::new();
+    while !input.is_empty() {
+        let (rest, value) = resp(input)?;
+        values.push(value);
+        input = rest;
+    }
+    Ok((input, values))
+}
+
+/// Parses as many complete RESP values as the input holds, e.g. the bytes read from a socket so far,
+/// returning them along with the unconsumed remainder, i.e. the beginning of a partial value (or nothing)
+/// which should be kept until more bytes arrive. Fails if any of the values is malformed,
+/// as the stream can't be resynchronized after that.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::{decode_frames, Resp};
+///
+/// let (parsed, rest) = decode_frames(&b"+OK\r\n:8\r\n$5\r\nhel"[..]).unwrap();
+/// assert_eq!(parsed, vec![Resp::SimpleString(b"OK".to_vec()), Resp::Integer(8)]);
+/// assert_eq!(rest, b"$5\r\nhel");
+/// ```
+pub fn decode_frames(mut input: &[u8]) -> Result<(Vec<Resp>, &[u8]), RespError> {
+    let mut values = Vec::new();
+    loop {
+        match resp_streaming(input) {
+            Ok((rest, value)) => {
+                values.push(value);
+                input = rest;
+            }
+            Err(Err::Incomplete(_)) => return Ok((values, input)),
+            Err(Err::Error(error)) | Err(Err::Failure(error)) => return Err(error),
+        }
+    }
+}
+
+/// Parses exactly `n` RESP values, e.g. replies to `n` pipelined commands, returning the rest of the input.
+/// Like [`resp`], fails if the input ends before all of them are complete.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+/// * `n` - number of values to parse
+///
+/// # Examples
+/// ```
+/// use respirator::{parse_n, Resp};
+///
+/// let (rest, parsed) = parse_n(&b"+OK\r\n:8\r\n$-1\r\n"[..], 2).unwrap();
+/// assert_eq!(parsed, vec![Resp::SimpleString(b"OK".to_vec()), Resp::Integer(8)]);
+/// assert_eq!(rest, b"$-1\r\n");
+/// ```
+pub fn parse_n(mut input: &[u8], n: usize) -> IResult<&[u8], Vec<Resp>, RespError> {
+    // Every value takes at least 3 bytes, so the input bounds how many of them fit.
+    let mut values = Vec::with_capacity(n.min(input.len() / 3));
+    for _ in 0..n {
+        let (rest, value) = resp(input)?;
+        values.push(value);
+        input = rest;
+    }
+    Ok((input, values))
+}
+
+/// Parses exactly one RESP value, failing with `RespError::TrailingBytes` if any input remains after it,
+/// e.g. in a request handler expecting a single command per buffer.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+///
+/// # Examples
+/// ```
+/// use respirator::{parse_exact, Resp, RespError};
+///
+/// assert_eq!(parse_exact(&b":8\r\n"[..]), Ok(Resp::Integer(8)));
+/// assert_eq!(parse_exact(&b":8\r\n+OK\r\n"[..]), Err(RespError::TrailingBytes));
+/// ```
+pub fn parse_exact(input: &[u8]) -> Result<Resp, RespError> {
+    match resp(input) {
+        Ok((&[], value)) => Ok(value),
+        Ok(_) => Err(RespError::TrailingBytes),
+        Err(Err::Incomplete(_)) => Err(RespError::UnexpectedEof),
+        Err(Err::Error(error)) | Err(Err::Failure(error)) => Err(error),
+    }
+}
+
+/// Measures complete RESP value at the head of the input without building it,
+/// e.g. to split a socket buffer into frames before parsing them.
+/// Returns `Ok(None)` if the input does not contain a complete value yet.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be probed
+///
+/// # Examples
+/// ```
+/// use respirator::try_frame_len;
+///
+/// assert_eq!(try_frame_len(&b"*1\r\n$2\r\nOK\r\n+next"[..]), Ok(Some(12)));
+/// assert_eq!(try_frame_len(&b"*1\r\n$2\r\nO"[..]), Ok(None));
+/// assert!(try_frame_len(&b"?\r\n"[..]).is_err());
+/// ```
+pub fn try_frame_len(input: &[u8]) -> Result<Option<usize>, RespError> {
+    match frame_len(input, &ParseConfig::default()) {
+        Ok(len) => Ok(Some(len)),
+        Err(Err::Incomplete(_)) => Ok(None),
+        Err(Err::Error(error)) | Err(Err::Failure(error)) => Err(error),
+    }
+}
+
+/// Measures complete RESP value like [`try_frame_len`], according to the given config,
+/// reporting `Incomplete` with the number of bytes needed at least while the value is incomplete.
+pub(crate) fn frame_len(input: &[u8], config: &ParseConfig) -> Result<usize, Err<RespError>> {
+    let (rest, Skip) = value::<Skip>(input, Context::new(true, *config))?;
+    Ok(input.len() - rest.len())
+}
+
+/// Parsing state shared by all type parsers.
+#[derive(Clone, Copy)]
+pub(crate) struct Context {
+    /// Whether to return `Incomplete` (streaming) or `Error` (complete) on exhausted input.
+    streaming: bool,
+    /// Options of the parser, including limits to enforce.
+    pub(crate) limits: ParseConfig,
+    /// Number of aggregates enclosing currently parsed value.
+    depth: usize,
+}
+
+impl Context {
+    pub(crate) fn new(streaming: bool, limits: ParseConfig) -> Self {
+        Context {
+            streaming,
+            limits,
+            depth: 0,
+        }
+    }
+
+    /// Context for elements of an aggregate, failing if it would exceed maximum depth.
+    pub(crate) fn nested(self) -> Result<Self, Err<RespError>> {
+        if self.depth >= self.limits.max_depth {
+            return Err(Err::Error(RespError::DepthExceeded));
+        }
+        Ok(Context {
+            depth: self.depth + 1,
+            ..self
+        })
+    }
+}
+
+pub(crate) fn value<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    located(input, ctx).map_err(|(error, _)| error)
+}
+
+/// Result of parsing which on failure also holds the input starting at the value that failed to parse.
+type Located<'a, O> = Result<(&'a [u8], O), (Err<RespError>, &'a [u8])>;
+
+fn located<'a, V: FromWire<'a>>(input: &'a [u8], ctx: Context) -> Located<'a, V> {
+    let (rest, progress) = node(input, ctx).map_err(|error| (error, input))?;
+    build(rest, ctx, progress)
+}
+
+/// Parses a scalar or the header of an aggregate, whose elements are left to [`build`].
+fn node<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], Progress<V>, RespError> {
+    let (rest, val) = take_bytes(input, 1, ctx)?;
+    match val[0] {
+        b'+' => complete(simple_string(rest, ctx)),
+        b':' => complete(integer(rest, ctx)),
+        b'-' => complete(error(rest, ctx)),
+        b'$' => complete(bulk_string(rest, ctx)),
+        b'*' => array_header(rest, ctx),
+        b',' => complete(double(rest, ctx)),
+        b'#' => complete(boolean(rest, ctx)),
+        b'_' => complete(null(rest, ctx)),
+        b'%' => entries_header(rest, ctx, Entries::Map),
+        b'~' => elements_header(rest, ctx, Elements::Set),
+        b'>' => elements_header(rest, ctx, Elements::Push),
+        b'(' => complete(big_number(rest, ctx)),
+        b'=' => complete(verbatim_string(rest, ctx)),
+        b'!' => complete(bulk_error(rest, ctx)),
+        b'|' => entries_header(rest, ctx, Entries::Attribute),
+        byte => Err(Err::Error(RespError::UnknownType(byte))),
+    }
+}
+
+fn complete<V>(parsed: IResult<&[u8], V, RespError>) -> IResult<&[u8], Progress<V>, RespError> {
+    parsed.map(|(rest, val)| (rest, Progress::Complete(val)))
+}
+
+/// Finishes parsing a value, keeping aggregates whose elements are being parsed on an explicit stack
+/// rather than recursing, so nesting depth is bounded by [`ParseConfig::max_depth`] only, not by the native stack.
+fn build<'a, V: FromWire<'a>>(
+    mut input: &'a [u8],
+    ctx: Context,
+    mut progress: Progress<V>,
+) -> Located<'a, V> {
+    let mut stack = Vec::new();
+    loop {
+        match progress {
+            Progress::Pending(frame) => {
+                if frame.is_open() {
+                    match marker(input, ctx, b'.') {
+                        Ok((rest, true)) => {
+                            input = rest;
+                            progress = frame.end();
+                            continue;
+                        }
+                        Ok(_) if frame.is_full() => {
+                            return Err((Err::Error(RespError::LimitExceeded), input))
+                        }
+                        Ok(_) => {}
+                        Err(error) => return Err((error, input)),
+                    }
+                }
+                stack.push(frame);
+                let ctx = Context {
+                    depth: ctx.depth + stack.len(),
+                    ..ctx
+                };
+                let (rest, next) = node(input, ctx).map_err(|error| (error, input))?;
+                input = rest;
+                progress = next;
+            }
+            Progress::Complete(val) => match stack.pop() {
+                Some(frame) => progress = frame.push(val),
+                None => return Ok((input, val)),
+            },
+        }
+    }
+}
+
+/// State of a value being built.
+enum Progress<V> {
+    /// Aggregate still waiting for some of its elements.
+    Pending(Frame<V>),
+    /// Fully parsed value.
+    Complete(V),
+}
+
+/// Aggregate whose elements are being parsed.
+enum Frame<V> {
+    /// Array, Set or Push with `remaining` elements left to parse,
+    /// or for `streamed` one of unknown length, with `remaining` elements allowed by the limits before its end.
+    Elements {
+        kind: Elements,
+        remaining: usize,
+        values: Vec<V>,
+        streamed: bool,
+    },
+    /// Map or Attribute with `remaining` entries left to parse, or allowed before the end if `streamed`,
+    /// `key` of the current entry is kept until its value is parsed.
+    Entries {
+        kind: Entries,
+        remaining: usize,
+        entries: Vec<(V, V)>,
+        key: Option<V>,
+        streamed: bool,
+    },
+    /// Attribute with all of its entries parsed, waiting for the value it is attached to.
+    Attributed { attributes: Vec<(V, V)> },
+}
+
+#[derive(Clone, Copy)]
+enum Elements {
+    Array,
+    Set,
+    Push,
+}
+
+#[derive(Clone, Copy)]
+enum Entries {
+    Map,
+    Attribute,
+}
+
+impl<'a, V: FromWire<'a>> Frame<V> {
+    /// Adds parsed element to the aggregate.
+    fn push(self, val: V) -> Progress<V> {
+        match self {
+            Frame::Elements {
+                kind,
+                remaining,
+                mut values,
+                streamed,
+            } => {
+                values.push(val);
+                Frame::Elements {
+                    kind,
+                    remaining: remaining - 1,
+                    values,
+                    streamed,
+                }
+                .progress()
+            }
+            Frame::Entries {
+                kind,
+                remaining,
+                entries,
+                key: None,
+                streamed,
+            } => Progress::Pending(Frame::Entries {
+                kind,
+                remaining,
+                entries,
+                key: Some(val),
+                streamed,
+            }),
+            Frame::Entries {
+                kind,
+                remaining,
+                mut entries,
+                key: Some(key),
+                streamed,
+            } => {
+                entries.push((key, val));
+                Frame::Entries {
+                    kind,
+                    remaining: remaining - 1,
+                    entries,
+                    key: None,
+                    streamed,
+                }
+                .progress()
+            }
+            Frame::Attributed { attributes } => Progress::Complete(V::attribute(attributes, val)),
+        }
+    }
+
+    /// Checks whether the aggregate is of unknown length and waits for either its next element or its end.
+    fn is_open(&self) -> bool {
+        matches!(
+            self,
+            Frame::Elements { streamed: true, .. }
+                | Frame::Entries {
+                    streamed: true,
+                    key: None,
+                    ..
+                }
+        )
+    }
+
+    /// Checks whether the aggregate has no more elements to parse, or allowed by the limits if it is of unknown length.
+    fn is_full(&self) -> bool {
+        matches!(
+            self,
+            Frame::Elements { remaining: 0, .. } | Frame::Entries { remaining: 0, .. }
+        )
+    }
+
+    /// Completes the aggregate if there are no more elements to parse.
+    fn progress(self) -> Progress<V> {
+        match self {
+            Frame::Elements {
+                streamed: false,
+                remaining: 0,
+                ..
+            }
+            | Frame::Entries {
+                streamed: false,
+                remaining: 0,
+                ..
+            } => self.end(),
+            frame => Progress::Pending(frame),
+        }
+    }
+
+    /// Completes the aggregate, ignoring any elements it still waits for.
+    fn end(self) -> Progress<V> {
+        match self {
+            Frame::Elements { kind, values, .. } => Progress::Complete(match kind {
+                Elements::Array => V::array(Some(values)),
+                Elements::Set => V::set(values),
+                Elements::Push => V::push(values),
+            }),
+            Frame::Entries {
+                kind: Entries::Map,
+                entries,
+                ..
+            } => Progress::Complete(V::map(entries)),
+            Frame::Entries {
+                kind: Entries::Attribute,
+                entries,
+                ..
+            } => Progress::Pending(Frame::Attributed {
+                attributes: entries,
+            }),
+            frame => Progress::Pending(frame),
+        }
+    }
+}
+
+/// Parses line consisting of the marker byte alone, if the input starts with it.
+/// RESP3 uses `?` in place of the length of streamed values and `.` as the end of streamed aggregates.
+fn marker(input: &[u8], ctx: Context, byte: u8) -> IResult<&[u8], bool, RespError> {
+    match input.first() {
+        Some(first) if *first == byte => {
+            let (rest, _) = line_end(&input[1..], ctx)?;
+            Ok((rest, true))
+        }
+        _ => Ok((input, false)),
+    }
+}
+
+pub(crate) fn simple_string<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (input, val) = line(input, ctx)?;
+    strict_utf8(val, ctx)?;
+    Ok((input, V::simple_string(val)))
+}
+
+pub(crate) fn integer<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, val) = line(input, ctx)?;
+    let val = signed_decimal(val, ctx.limits.integer_overflow).map_err(Err::Error)?;
+    Ok((rest, V::integer(val)))
+}
+
+fn signed_decimal(val: &[u8], overflow: IntegerOverflow) -> Result<i64, RespError> {
+    if !is_signed_decimal(val) {
+        return Err(RespError::InvalidInteger);
+    }
+    let (negative, digits) = match val {
+        [b'-', digits @ ..] => (true, digits),
+        [b'+', digits @ ..] => (false, digits),
+        digits => (false, digits),
+    };
+    // Grammar is already verified, so accumulating ASCII digits can only fail on overflow.
+    // Negative values are accumulated downwards, so that `i64::MIN` fits.
+    let mut acc: i64 = 0;
+    for digit in digits {
+        let digit = i64::from(digit - b'0');
+        let next = acc.checked_mul(10).and_then(|acc| {
+            if negative {
+                acc.checked_sub(digit)
+            } else {
+                acc.checked_add(digit)
+            }
+        });
+        acc = match (next, overflow) {
+            (Some(next), _) => next,
+            (None, IntegerOverflow::Error) => return Err(RespError::IntegerOverflow),
+            (None, IntegerOverflow::Saturate) if negative => return Ok(i64::MIN),
+            (None, IntegerOverflow::Saturate) => return Ok(i64::MAX),
+        };
+    }
+    Ok(acc)
+}
+
+/// Checks signed decimal grammar of RESP, i.e. optional `+` or `-` sign followed by at least one ASCII digit.
+/// Leading zeros are allowed, any whitespace is not.
+pub(crate) fn is_signed_decimal(val: &[u8]) -> bool {
+    let digits = match val {
+        [b'+' | b'-', digits @ ..] => digits,
+        digits => digits,
+    };
+    !digits.is_empty() && digits.iter().all(u8::is_ascii_digit)
+}
+
+pub(crate) fn error<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (input, val) = line(input, ctx)?;
+    strict_utf8(val, ctx)?;
+    Ok((input, V::error(val)))
+}
+
+/// Fails on payloads which are not valid UTF-8, if strict mode is enabled.
+fn strict_utf8(val: &[u8], ctx: Context) -> Result<(), Err<RespError>> {
+    if ctx.limits.strict_utf8 && core::str::from_utf8(val).is_err() {
+        return Err(Err::Error(RespError::InvalidUtf8));
+    }
+    Ok(())
+}
+
+pub(crate) fn double<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, val) = line(input, ctx)?;
+    let value = parse_double(val).ok_or(Err::Error(RespError::InvalidDouble))?;
+    Ok((rest, V::double(value)))
+}
+
+/// Parses payload of a Double, in any notation `f64::from_str` accepts (e.g. `1e10` or `-0`, keeping its sign),
+/// including `inf`, `-inf` and `nan`.
+pub(crate) fn parse_double(val: &[u8]) -> Option<f64> {
+    match val {
+        b"inf" => Some(f64::INFINITY),
+        b"-inf" => Some(f64::NEG_INFINITY),
+        b"nan" => Some(f64::NAN),
+        _ => core::str::from_utf8(val).ok()?.parse().ok(),
+    }
+}
+
+pub(crate) fn boolean<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, val) = payload(input, 1, ctx)?;
+    match val[0] {
+        b't' => Ok((rest, V::boolean(true))),
+        b'f' => Ok((rest, V::boolean(false))),
+        _ => Err(Err::Error(RespError::InvalidBoolean)),
+    }
+}
+
+pub(crate) fn null<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (input, _) = line_end(input, ctx)?;
+    Ok((input, V::null()))
+}
+
+pub(crate) fn big_number<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, val) = line(input, ctx)?;
+    if !is_signed_decimal(val) {
+        return Err(Err::Error(RespError::InvalidBigNumber));
+    }
+    Ok((rest, V::big_number(val)))
+}
+
+pub(crate) fn bulk_string<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, streamed) = marker(input, ctx, b'?')?;
+    if streamed {
+        return streamed_string(rest, ctx);
+    }
+    let (rest, len) = match nullable_length(input, ctx)? {
+        (rest, Some(len)) => (rest, len),
+        (rest, None) => return Ok((rest, V::bulk_string(None))),
+    };
+    within_limit(len, ctx.limits.max_bulk_len)?;
+    let (input, val) = payload(rest, len, ctx)?;
+
+    Ok((input, V::bulk_string(Some(val))))
+}
+
+/// Parses chunks of a streamed string, each prefixed with `;` and its length, up to the final chunk of length 0.
+fn streamed_string<'a, V: FromWire<'a>>(
+    mut input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let mut chunks = Vec::new();
+    let mut total: usize = 0;
+    loop {
+        let (rest, val) = take_bytes(input, 1, ctx)?;
+        if val[0] != b';' {
+            return Err(Err::Error(RespError::InvalidChunk));
+        }
+        let (rest, len) = length(rest, ctx)?;
+        if len == 0 {
+            return Ok((rest, V::streamed_string(chunks)));
+        }
+        total = total.saturating_add(len);
+        within_limit(total, ctx.limits.max_bulk_len)?;
+        let (rest, chunk) = payload(rest, len, ctx)?;
+        chunks.push(chunk);
+        input = rest;
+    }
+}
+
+pub(crate) fn bulk_error<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, len) = length(input, ctx)?;
+    within_limit(len, ctx.limits.max_bulk_len)?;
+    let (input, val) = payload(rest, len, ctx)?;
+    Ok((input, V::bulk_error(val)))
+}
+
+pub(crate) fn verbatim_string<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, len) = length(input, ctx)?;
+    within_limit(len, ctx.limits.max_bulk_len)?;
+    let (rest, val) = payload(rest, len, ctx)?;
+    match val {
+        [f1, f2, f3, b':', data @ ..] => Ok((rest, V::verbatim_string([*f1, *f2, *f3], data))),
+        _ => Err(Err::Error(RespError::InvalidVerbatimString)),
+    }
+}
+
+/// Parses length which may be `-1`, denoting null, as None. Other lengths are unsigned, see [`length`].
+pub(crate) fn nullable_length(
+    input: &[u8],
+    ctx: Context,
+) -> IResult<&[u8], Option<usize>, RespError> {
+    let (rest, len) = line(input, ctx)?;
+    if len == b"-1" {
+        return Ok((rest, None));
+    }
+    match unsigned_decimal(len) {
+        Some(len) => Ok((rest, Some(len))),
+        None => Err(Err::Error(RespError::InvalidLength)),
+    }
+}
+
+/// Parses length, which unlike Integer is never signed, so `+3` and `-0` are rejected.
+/// Lengths which don't fit in `usize`, e.g. on 32-bit targets, are rejected rather than truncated.
+pub(crate) fn length(input: &[u8], ctx: Context) -> IResult<&[u8], usize, RespError> {
+    let (rest, len) = line(input, ctx)?;
+    match unsigned_decimal(len) {
+        Some(len) => Ok((rest, len)),
+        None => Err(Err::Error(RespError::InvalidLength)),
+    }
+}
+
+/// Parses at least one ASCII digit, None if there are other bytes or the value overflows.
+fn unsigned_decimal(val: &[u8]) -> Option<usize> {
+    if val.is_empty() {
+        return None;
+    }
+    val.iter().try_fold(0usize, |acc, digit| {
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+        acc.checked_mul(10)?.checked_add(usize::from(digit - b'0'))
+    })
+}
+
+pub(crate) fn array<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, progress) = array_header(input, ctx)?;
+    build(rest, ctx, progress).map_err(|(error, _)| error)
+}
+
+pub(crate) fn map<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, progress) = entries_header(input, ctx, Entries::Map)?;
+    build(rest, ctx, progress).map_err(|(error, _)| error)
+}
+
+pub(crate) fn set<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, progress) = elements_header(input, ctx, Elements::Set)?;
+    build(rest, ctx, progress).map_err(|(error, _)| error)
+}
+
+pub(crate) fn push<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, progress) = elements_header(input, ctx, Elements::Push)?;
+    build(rest, ctx, progress).map_err(|(error, _)| error)
+}
+
+pub(crate) fn attribute<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], V, RespError> {
+    let (rest, progress) = entries_header(input, ctx, Entries::Attribute)?;
+    build(rest, ctx, progress).map_err(|(error, _)| error)
+}
+
+fn array_header<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+) -> IResult<&'a [u8], Progress<V>, RespError> {
+    if input.first() == Some(&b'?') {
+        return elements_header(input, ctx, Elements::Array);
+    }
+    let (rest, len) = match nullable_length(input, ctx)? {
+        (rest, Some(len)) => (rest, len),
+        (rest, None) => return Ok((rest, Progress::Complete(V::array(None)))),
+    };
+    within_limit(len, ctx.limits.max_array_len)?;
+    ctx.nested()?;
+    let frame = Frame::Elements {
+        kind: Elements::Array,
+        remaining: len,
+        values: Vec::with_capacity(capacity(len, 1, rest, ctx)),
+        streamed: false,
+    };
+    Ok((rest, frame.progress()))
+}
+
+fn elements_header<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+    kind: Elements,
+) -> IResult<&'a [u8], Progress<V>, RespError> {
+    ctx.nested()?;
+    let (rest, streamed) = marker(input, ctx, b'?')?;
+    if streamed {
+        let frame = Frame::Elements {
+            kind,
+            remaining: ctx.limits.max_array_len,
+            values: Vec::new(),
+            streamed,
+        };
+        return Ok((rest, frame.progress()));
+    }
+    let (rest, len) = length(rest, ctx)?;
+    within_limit(len, ctx.limits.max_array_len)?;
+    let frame = Frame::Elements {
+        kind,
+        remaining: len,
+        values: Vec::with_capacity(capacity(len, 1, rest, ctx)),
+        streamed,
+    };
+    Ok((rest, frame.progress()))
+}
+
+fn entries_header<'a, V: FromWire<'a>>(
+    input: &'a [u8],
+    ctx: Context,
+    kind: Entries,
+) -> IResult<&'a [u8], Progress<V>, RespError> {
+    ctx.nested()?;
+    // Only Maps may be streamed, Attributes always declare their length.
+    let (rest, streamed) = match kind {
+        Entries::Map => marker(input, ctx, b'?')?,
+        Entries::Attribute => (input, false),
+    };
+    if streamed {
+        let frame = Frame::Entries {
+            kind,
+            remaining: ctx.limits.max_map_len,
+            entries: Vec::new(),
+            key: None,
+            streamed,
+        };
+        return Ok((rest, frame.progress()));
+    }
+    let (rest, len) = length(rest, ctx)?;
+    within_limit(len, ctx.limits.max_map_len)?;
+    let frame = Frame::Entries {
+        kind,
+        remaining: len,
+        entries: Vec::with_capacity(capacity(len, 2, rest, ctx)),
+        key: None,
+        streamed,
+    };
+    Ok((rest, frame.progress()))
+}
+
+/// Capacity to reserve up front for `len` elements of `values` values each, bounded by the number of values
+/// the remaining input can hold, so that a bogus length can't reserve memory for data which never comes.
+/// Aggregates grow as usual while more input gets parsed in streaming mode.
+fn capacity(len: usize, values: usize, input: &[u8], ctx: Context) -> usize {
+    // Smallest value is a type byte followed by line ending, e.g. `_\r\n`.
+    let min_value_len = if ctx.limits.lenient_line_endings {
+        2
+    } else {
+        3
+    };
+    len.min(input.len() / min_value_len / values)
+}
+
+pub(crate) fn within_limit(len: usize, max: usize) -> Result<(), Err<RespError>> {
+    if len > max {
+        return Err(Err::Error(RespError::LimitExceeded));
+    }
+    Ok(())
+}
+
+pub(crate) fn take_bytes(
+    input: &[u8],
+    len: usize,
+    ctx: Context,
+) -> IResult<&[u8], &[u8], RespError> {
+    if ctx.streaming {
+        bytes::streaming::take(len)(input)
+    } else {
+        bytes::complete::take(len)(input)
+    }
+}
+
+fn line_end(input: &[u8], ctx: Context) -> IResult<&[u8], &[u8], RespError> {
+    if ctx.limits.lenient_line_endings && input.first() == Some(&b'\n') {
+        return Ok((&input[1..], &input[..1]));
+    }
+    if ctx.streaming {
+        // nom asks for the whole CRLF even if CR is already buffered.
+        if input == b"\r" {
+            return Err(Err::Incomplete(Needed::new(1)));
+        }
+        character::streaming::crlf(input)
+    } else {
+        character::complete::crlf(input)
+    }
+}
+
+/// Takes bytes up to the first CR or LF, which must start the terminating CRLF (or be a bare LF in lenient mode).
+/// Therefore line-based payloads can't contain a stray CR or LF.
+pub(crate) fn line(input: &[u8], ctx: Context) -> IResult<&[u8], &[u8], RespError> {
+    match input
+        .iter()
+        .position(|byte| *byte == b'\r' || *byte == b'\n')
+    {
+        Some(end) => {
+            let (val, rest) = input.split_at(end);
+            let (rest, _) = line_end(rest, ctx)?;
+            Ok((rest, val))
+        }
+        None if ctx.streaming => Err(Err::Incomplete(Needed::Unknown)),
+        None => Err(Err::Error(RespError::MissingCrlf)),
+    }
+}
+
+/// Takes length prefixed payload followed by its line ending.
+/// In streaming mode, reports exactly how many bytes are missing while the payload is incomplete,
+/// including the line ending following it.
+fn payload(input: &[u8], len: usize, ctx: Context) -> IResult<&[u8], &[u8], RespError> {
+    if ctx.streaming && input.len() < len {
+        let line_end_len = if ctx.limits.lenient_line_endings {
+            1
+        } else {
+            2
+        };
+        let missing = (len - input.len()).saturating_add(line_end_len);
+        return Err(Err::Incomplete(Needed::new(missing)));
+    }
+    let (input, val) = take_bytes(input, len, ctx)?;
+    let (input, _) = line_end(input, ctx)?;
+    Ok((input, val))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_simple_strings() {
+        let input = &b"+OK - seems good.\r\n"[..];
+        let parsed = resp(input).unwrap();
+        if let Resp::SimpleString(parsed) = parsed.1 {
+            assert_eq!(parsed, b"OK - seems good.".to_vec());
+        } else {
+            panic!("Error parsing SimpleString");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_simple_string() {
+        let corrupted_input = &b"+OK - seems bad.\r"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn fails_on_stray_line_endings() {
+        for corrupted_input in [
+            &b"+a\rb\r\n"[..],
+            &b"+a\nb\r\n"[..],
+            &b"-ERR\rmessage\r\n"[..],
+            &b":1\r2\r\n"[..],
+        ] {
+            if let Err(Err::Error(error)) = resp(corrupted_input) {
+                assert_eq!(error, RespError::MissingCrlf);
+            } else {
+                panic!("Error rejecting stray line ending");
+            }
+            assert!(matches!(
+                resp_streaming(corrupted_input),
+                Err(Err::Error(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn parses_integers() {
+        let input = &b":12345\r\n"[..];
+        let parsed = resp(input).unwrap();
+        if let Resp::Integer(parsed) = parsed.1 {
+            assert_eq!(parsed, 12345);
+        } else {
+            panic!("Error parsing Integer");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_integer() {
+        let corrupted_input = &b":OK - seems bad.\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn returns_error_on_corrupted_integer() {
+        let parsed = resp(&b":notanumber\r\n"[..]);
+        if let Err(Err::Error(error)) = parsed {
+            assert_eq!(error, RespError::InvalidInteger);
+        } else {
+            panic!("Error rejecting corrupted Integer");
+        }
+    }
+
+    #[test]
+    fn returns_error_on_overflowing_integer() {
+        let parsed = resp(&b":99999999999999999999\r\n"[..]);
+        assert_eq!(parsed, Err(Err::Error(RespError::IntegerOverflow)));
+    }
+
+    #[test]
+    fn saturates_overflowing_integer() {
+        let limits = ParseConfig {
+            integer_overflow: IntegerOverflow::Saturate,
+            ..ParseConfig::default()
+        };
+        for (input, expected) in [
+            (&b":9223372036854775808\r\n"[..], i64::MAX),
+            (&b":+99999999999999999999\r\n"[..], i64::MAX),
+            (&b":-9223372036854775809\r\n"[..], i64::MIN),
+            (&b":-9223372036854775808\r\n"[..], i64::MIN),
+        ] {
+            let (_, parsed) = resp_with_limits(input, &limits).unwrap();
+            assert_eq!(parsed, Resp::Integer(expected));
+        }
+        assert!(resp_with_limits(&b":1a\r\n"[..], &limits).is_err());
+    }
+
+    #[test]
+    fn parses_signed_integers() {
+        for (input, expected) in [
+            (&b":+8\r\n"[..], 8),
+            (&b":-8\r\n"[..], -8),
+            (&b":007\r\n"[..], 7),
+            (&b":-0\r\n"[..], 0),
+            (&b":-9223372036854775808\r\n"[..], i64::MIN),
+            (&b":9223372036854775807\r\n"[..], i64::MAX),
+        ] {
+            let (_, parsed) = resp(input).unwrap();
+            assert_eq!(parsed, Resp::Integer(expected));
+        }
+    }
+
+    #[test]
+    fn returns_error_on_malformed_integer_grammar() {
+        for corrupted_input in [
+            &b": 8\r\n"[..],
+            &b":8 \r\n"[..],
+            &b":\r\n"[..],
+            &b":+\r\n"[..],
+            &b":--8\r\n"[..],
+            &b":+-8\r\n"[..],
+            &b":0x10\r\n"[..],
+        ] {
+            assert!(matches!(resp(corrupted_input), Err(Err::Error(_))));
+        }
+    }
+
+    #[test]
+    fn parses_errors() {
+        let input = &b"-this is an error\r\n"[..];
+        let parsed = resp(input).unwrap();
+        if let Resp::Error(parsed) = parsed.1 {
+            assert_eq!(parsed, b"this is an error".to_vec());
+        } else {
+            panic!("Error parsing Error");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_error() {
+        let corrupted_input = &b"-an error - seems bad.\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn parses_bulk_string() {
+        let input = &b"$4\r\ngood\r\n"[..];
+        let parsed = resp(input).unwrap();
+        if let Resp::BulkString(Some(parsed)) = parsed.1 {
+            assert_eq!(parsed, b"good".to_vec());
+        } else {
+            panic!("Error parsing BulkString");
+        }
+    }
+
+    #[test]
+    fn parses_empty_bulk_string() {
+        let input = &b"$0\r\n\r\n"[..];
+        let (input, parsed) = resp(input).unwrap();
+        if let Resp::BulkString(Some(parsed)) = parsed {
+            assert!(parsed.is_empty());
+        } else {
+            panic!("Error parsing BulkString");
+        }
+        assert_eq!(input, &[]);
+    }
+
+    #[test]
+    fn parses_null_bulk_string() {
+        let input = &b"$-1\r\n"[..];
+        let (input, parsed) = resp(input).unwrap();
+        if let Resp::BulkString(None) = parsed {
+        } else {
+            panic!("Error parsing BulkString");
+        }
+        assert_eq!(input, &[]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_negative_bulk_string_length() {
+        let corrupted_input = &b"$-2\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn rejects_signed_lengths() {
+        for input in [
+            &b"$+3\r\nfoo\r\n"[..],
+            &b"$-0\r\n\r\n"[..],
+            &b"*+1\r\n:1\r\n"[..],
+            &b"*-0\r\n"[..],
+            &b"%+1\r\n:1\r\n:2\r\n"[..],
+            &b"$ 3\r\nfoo\r\n"[..],
+        ] {
+            assert_eq!(resp(input), Err(Err::Error(RespError::InvalidLength)));
+        }
+        let (_, parsed) = resp(&b"$03\r\nfoo\r\n"[..]).unwrap();
+        assert_eq!(parsed, Resp::BulkString(Some(b"foo".to_vec())));
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_bulk_string() {
+        let corrupted_input = &b"$4\r\nbad\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn returns_error_on_corrupted_length() {
+        for corrupted_input in [
+            &b"$abc\r\n"[..],
+            &b"*x\r\n"[..],
+            &b"%x\r\n"[..],
+            &b"~-1\r\n"[..],
+            &b"=abc\r\n"[..],
+        ] {
+            assert!(matches!(resp(corrupted_input), Err(Err::Error(_))));
+        }
+    }
+
+    #[test]
+    fn parses_array() {
+        let input = &b"*2\r\n$2\r\nOK\r\n$4\r\nResp\r\n"[..];
+        let parsed = resp(input).unwrap();
+
+        if let Resp::Array(Some(parsed)) = parsed.1 {
+            if let [Resp::BulkString(Some(str1)), Resp::BulkString(Some(str2))] = &parsed[..] {
+                assert_eq!(*str1, b"OK".to_vec());
+                assert_eq!(*str2, b"Resp".to_vec());
+            } else {
+                panic!("Error parsing Array");
+            }
+        } else {
+            panic!("Error parsing Array");
+        }
+    }
+
+    #[test]
+    fn parses_empty_array() {
+        let input = &b"*0\r\n"[..];
+        let parsed = resp(input).unwrap();
+
+        if let Resp::Array(Some(parsed)) = parsed.1 {
+            assert!(parsed.is_empty());
+        } else {
+            panic!("Error parsing Array");
+        }
+    }
+
+    #[test]
+    fn parses_null_array() {
+        let input = &b"*-1\r\n"[..];
+        let parsed = resp(input).unwrap();
+
+        if let Resp::Array(None) = parsed.1 {
+        } else {
+            panic!("Error parsing Array");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_negative_array_length() {
+        let corrupted_input = &b"*-2\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn parses_doubles() {
+        let input = &b",2.5\r\n"[..];
+        let parsed = resp(input).unwrap();
+        if let Resp::Double(parsed) = parsed.1 {
+            assert_eq!(parsed, 2.5);
+        } else {
+            panic!("Error parsing Double");
+        }
+
+        let input = &b",10\r\n"[..];
+        let parsed = resp(input).unwrap();
+        if let Resp::Double(parsed) = parsed.1 {
+            assert_eq!(parsed, 10.0);
+        } else {
+            panic!("Error parsing Double");
+        }
+    }
+
+    #[test]
+    fn parses_doubles_in_scientific_notation() {
+        for (input, expected) in [
+            (&b",1e10\r\n"[..], 1e10),
+            (&b",1.0e3\r\n"[..], 1000.0),
+            (&b",-2.5E-2\r\n"[..], -0.025),
+            (&b",+1.5\r\n"[..], 1.5),
+        ] {
+            assert_eq!(resp(input), Ok((&b""[..], Resp::Double(expected))));
+        }
+        assert!(resp(&b",1e\r\n"[..]).is_err());
+    }
+
+    #[test]
+    fn preserves_negative_zero() {
+        let (_, parsed) = resp(&b",-0\r\n"[..]).unwrap();
+        if let Resp::Double(parsed) = parsed {
+            assert!(parsed == 0.0 && parsed.is_sign_negative());
+            assert_eq!(Resp::Double(parsed).encode(), b",-0\r\n".to_vec());
+        } else {
+            panic!("Error parsing Double");
+        }
+    }
+
+    #[test]
+    fn parses_special_doubles() {
+        let (_, inf) = resp(&b",inf\r\n"[..]).unwrap();
+        let (_, neg_inf) = resp(&b",-inf\r\n"[..]).unwrap();
+        let (_, nan) = resp(&b",nan\r\n"[..]).unwrap();
+        match (inf, neg_inf, nan) {
+            (Resp::Double(inf), Resp::Double(neg_inf), Resp::Double(nan)) => {
+                assert_eq!(inf, f64::INFINITY);
+                assert_eq!(neg_inf, f64::NEG_INFINITY);
+                assert!(nan.is_nan());
+            }
+            _ => panic!("Error parsing Double"),
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_double() {
+        let corrupted_input = &b",2.5abc\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn parses_booleans() {
+        let (_, parsed_true) = resp(&b"#t\r\n"[..]).unwrap();
+        let (_, parsed_false) = resp(&b"#f\r\n"[..]).unwrap();
+        if let (Resp::Boolean(parsed_true), Resp::Boolean(parsed_false)) =
+            (parsed_true, parsed_false)
+        {
+            assert!(parsed_true);
+            assert!(!parsed_false);
+        } else {
+            panic!("Error parsing Boolean");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_boolean() {
+        let corrupted_input = &b"#x\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn parses_null() {
+        let input = &b"_\r\n"[..];
+        let parsed = resp(input).unwrap();
+        if let Resp::Null = parsed.1 {
+        } else {
+            panic!("Error parsing Null");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_null() {
+        let corrupted_input = &b"_x\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn parses_map() {
+        let input = &b"%2\r\n+a\r\n:1\r\n+b\r\n:2\r\n"[..];
+        let parsed = resp(input).unwrap();
+
+        if let Resp::Map(parsed) = parsed.1 {
+            if let [(Resp::SimpleString(key1), Resp::Integer(value1)), (Resp::SimpleString(key2), Resp::Integer(value2))] =
+                &parsed[..]
+            {
+                assert_eq!(*key1, b"a".to_vec());
+                assert_eq!(*value1, 1);
+                assert_eq!(*key2, b"b".to_vec());
+                assert_eq!(*value2, 2);
+            } else {
+                panic!("Error parsing Map");
+            }
+        } else {
+            panic!("Error parsing Map");
+        }
+    }
+
+    #[test]
+    fn parses_nested_map() {
+        let input = &b"%2\r\n+a\r\n%1\r\n+b\r\n:2\r\n+c\r\n*1\r\n:3\r\n"[..];
+        let parsed = resp(input).unwrap();
+
+        if let Resp::Map(parsed) = parsed.1 {
+            if let [(_, Resp::Map(inner)), (_, Resp::Array(Some(values)))] = &parsed[..] {
+                assert_eq!(inner.len(), 1);
+                assert_eq!(values.len(), 1);
+            } else {
+                panic!("Error parsing Map");
+            }
+        } else {
+            panic!("Error parsing Map");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_map() {
+        let corrupted_input = &b"%2\r\n+a\r\n:1\r\n+b\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn parses_set() {
+        let input = &b"~3\r\n+a\r\n+b\r\n+a\r\n"[..];
+        let parsed = resp(input).unwrap();
+
+        if let Resp::Set(parsed) = parsed.1 {
+            if let [Resp::SimpleString(str1), Resp::SimpleString(str2), Resp::SimpleString(str3)] =
+                &parsed[..]
+            {
+                assert_eq!(*str1, b"a".to_vec());
+                assert_eq!(*str2, b"b".to_vec());
+                assert_eq!(*str3, b"a".to_vec());
+            } else {
+                panic!("Error parsing Set");
+            }
+        } else {
+            panic!("Error parsing Set");
+        }
+    }
+
+    #[test]
+    fn parses_push() {
+        let input = &b">2\r\n$7\r\nmessage\r\n$5\r\nhello\r\n"[..];
+        let parsed = resp(input).unwrap();
+
+        if let Resp::Push(parsed) = parsed.1 {
+            if let [Resp::BulkString(Some(str1)), Resp::BulkString(Some(str2))] = &parsed[..] {
+                assert_eq!(*str1, b"message".to_vec());
+                assert_eq!(*str2, b"hello".to_vec());
+            } else {
+                panic!("Error parsing Push");
+            }
+        } else {
+            panic!("Error parsing Push");
+        }
+    }
+
+    #[test]
+    fn parses_big_numbers() {
+        let input = &b"(-3492890328409238509324850943850943825024385\r\n"[..];
+        let parsed = resp(input).unwrap();
+        if let Resp::BigNumber(parsed) = parsed.1 {
+            assert_eq!(
+                parsed,
+                b"-3492890328409238509324850943850943825024385".to_vec()
+            );
+        } else {
+            panic!("Error parsing BigNumber");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_big_number() {
+        let corrupted_input = &b"(12a34\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_sign_only_big_number() {
+        let corrupted_input = &b"(-\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn parses_verbatim_string() {
+        let input = &b"=15\r\ntxt:Some string\r\n"[..];
+        let parsed = resp(input).unwrap();
+        if let Resp::VerbatimString { format, data } = parsed.1 {
+            assert_eq!(&format, b"txt");
+            assert_eq!(data, b"Some string".to_vec());
+        } else {
+            panic!("Error parsing VerbatimString");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_verbatim_string_without_format() {
+        let corrupted_input = &b"=11\r\nSome string\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn parses_bulk_error() {
+        let input = &b"!21\r\nSYNTAX invalid syntax\r\n"[..];
+        let parsed = resp(input).unwrap();
+        if let Resp::BulkError(parsed) = parsed.1 {
+            assert_eq!(parsed, b"SYNTAX invalid syntax".to_vec());
+        } else {
+            panic!("Error parsing BulkError");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_bulk_error() {
+        let corrupted_input = &b"!6\r\nSYNTAX invalid syntax\r\n"[..];
+        resp(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_utf8_in_strict_mode() {
+        let limits = ParseConfig {
+            strict_utf8: true,
+            ..ParseConfig::default()
+        };
+        for input in [&b"+a\xffb\r\n"[..], &b"-ERR \xc3\r\n"[..]] {
+            assert!(resp(input).is_ok());
+            assert_eq!(
+                resp_with_limits(input, &limits),
+                Err(Err::Error(RespError::InvalidUtf8))
+            );
+        }
+        assert!(resp_with_limits(&b"+za\xc5\xbc\xc3\xb3\xc5\x82\xc4\x87\r\n"[..], &limits).is_ok());
+    }
+
+    #[test]
+    fn rejects_non_ascii_headers() {
+        for corrupted_input in [
+            &b":\xff8\r\n"[..],
+            &b"$\xff1\r\na\r\n"[..],
+            &b"*1\xef\xbf\xbd\r\n:1\r\n"[..],
+        ] {
+            assert!(resp(corrupted_input).is_err());
+        }
+    }
+
+    #[test]
+    fn accepts_bare_line_feeds_in_lenient_mode() {
+        let limits = ParseConfig {
+            lenient_line_endings: true,
+            ..ParseConfig::default()
+        };
+        let input = &b"*3\n+OK\r\n$3\nfoo\n:8\n"[..];
+        assert!(resp(input).is_err());
+        let (rest, parsed) = resp_with_limits(input, &limits).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            parsed,
+            Resp::Array(Some(vec![
+                Resp::SimpleString(b"OK".to_vec()),
+                Resp::BulkString(Some(b"foo".to_vec())),
+                Resp::Integer(8),
+            ]))
+        );
+        assert!(resp_with_limits(&b"+a\rb\n"[..], &limits).is_err());
+    }
+
+    #[test]
+    fn parses_attribute() {
+        let input = &b"|1\r\n+key-popularity\r\n%1\r\n$1\r\na\r\n,0.1923\r\n*1\r\n:2039123\r\n"[..];
+        let (input, parsed) = resp(input).unwrap();
+        if let Resp::Attribute { attributes, value } = parsed {
+            assert_eq!(attributes.len(), 1);
+            assert_eq!(
+                attributes[0].0,
+                Resp::SimpleString(b"key-popularity".to_vec())
+            );
+            assert_eq!(*value, Resp::Array(Some(vec![Resp::Integer(2039123)])));
+        } else {
+            panic!("Error parsing Attribute");
+        }
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn limits_attribute_chain_depth() {
+        let mut input = b"|0\r\n".repeat(100_000);
+        input.extend_from_slice(b"+OK\r\n");
+        assert_eq!(resp(&input), Err(Err::Error(RespError::DepthExceeded)));
+    }
+
+    #[test]
+    fn fails_on_unknown_type_byte() {
+        let corrupted_input = &b"?unknown\r\n"[..];
+        let parsed = resp(corrupted_input);
+        if let Err(Err::Error(error)) = parsed {
+            assert_eq!(error, RespError::UnknownType(b'?'));
+        } else {
+            panic!("Error rejecting unknown type byte");
+        }
+    }
+
+    #[test]
+    fn formats_nested_debug() {
+        let input = &b"*2\r\n:1\r\n*1\r\n+OK\r\n"[..];
+        let (_, parsed) = resp(input).unwrap();
+        assert_eq!(
+            format!("{:?}", parsed),
+            "Array(Some([Integer(1), Array(Some([SimpleString([79, 75])]))]))"
+        );
+    }
+
+    #[test]
+    fn compares_parsed_values() {
+        let (_, parsed) = resp(&b":8\r\n"[..]).unwrap();
+        assert_eq!(parsed, Resp::Integer(8));
+
+        let (_, parsed) = resp(&b"*2\r\n+OK\r\n$-1\r\n"[..]).unwrap();
+        assert_eq!(
+            parsed,
+            Resp::Array(Some(vec![
+                Resp::SimpleString(b"OK".to_vec()),
+                Resp::BulkString(None)
+            ]))
+        );
+        assert_ne!(parsed, Resp::Array(None));
+    }
+
+    #[test]
+    fn clones_nested_values() {
+        let (_, parsed) = resp(&b"*2\r\n$2\r\nOK\r\n*1\r\n:1\r\n"[..]).unwrap();
+        let cloned = parsed.clone();
+        assert_eq!(parsed, cloned);
+    }
+
+    #[test]
+    fn returns_incomplete_when_streaming() {
+        for partial_input in [
+            &b""[..],
+            &b"+OK"[..],
+            &b"+OK\r"[..],
+            &b":12"[..],
+            &b"$4\r\nRe"[..],
+            &b"$4\r\nResp"[..],
+            &b"*2\r\n$2\r\nOK\r\n"[..],
+            &b"%1\r\n+a\r\n"[..],
+        ] {
+            assert!(matches!(
+                resp_streaming(partial_input),
+                Err(Err::Incomplete(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn parses_complete_input_when_streaming() {
+        let input = &b"*2\r\n$2\r\nOK\r\n:8\r\n+rest"[..];
+        let (input, parsed) = resp_streaming(input).unwrap();
+        assert_eq!(
+            parsed,
+            Resp::Array(Some(vec![
+                Resp::BulkString(Some(b"OK".to_vec())),
+                Resp::Integer(8)
+            ]))
+        );
+        assert_eq!(input, b"+rest");
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_input_when_streaming() {
+        let corrupted_input = &b"$4\r\nbad\r\n"[..];
+        resp_streaming(corrupted_input).unwrap();
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        let limits = ParseConfig {
+            max_depth: 3,
+            ..ParseConfig::default()
+        };
+        let input = &b"*1\r\n%1\r\n+a\r\n~1\r\n:1\r\n"[..];
+        assert!(resp_with_limits(input, &limits).is_ok());
+
+        for nested_input in [
+            &b"*1\r\n%1\r\n+a\r\n~1\r\n*0\r\n"[..],
+            &b">1\r\n>1\r\n>1\r\n>1\r\n:1\r\n"[..],
+        ] {
+            if let Err(Err::Error(error)) = resp_with_limits(nested_input, &limits) {
+                assert_eq!(error, RespError::DepthExceeded);
+            } else {
+                panic!("Error limiting nesting depth");
+            }
+        }
+    }
+
+    #[test]
+    fn limits_nesting_depth_by_default() {
+        let mut input = b"*1\r\n".repeat(100_000);
+        input.extend_from_slice(b":1\r\n");
+        if let Err(Err::Error(error)) = resp(&input) {
+            assert_eq!(error, RespError::DepthExceeded);
+        } else {
+            panic!("Error limiting nesting depth");
+        }
+    }
+
+    #[test]
+    fn parses_deep_nesting_without_recursion() {
+        let limits = ParseConfig {
+            max_depth: usize::MAX,
+            ..ParseConfig::default()
+        };
+        let mut input = b"*1\r\n%1\r\n|0\r\n".repeat(100_000);
+        input.extend_from_slice(b":1\r\n");
+        input.extend_from_slice(&b":2\r\n".repeat(100_000));
+        // Skip doesn't build a tree, whose recursive drop would overflow the stack instead.
+        let parsed = value::<Skip>(&input, Context::new(false, limits));
+        assert!(matches!(parsed, Ok((rest, Skip)) if rest.is_empty()));
+    }
+
+    #[test]
+    fn locates_innermost_malformed_value() {
+        let input = &b"%1\r\n+key\r\n*2\r\n:1\r\n~1\r\n#x\r\n"[..];
+        let error = resp_located(input).unwrap_err();
+        assert_eq!(error.error, RespError::InvalidBoolean);
+        assert_eq!(&input[error.offset..], b"#x\r\n");
+
+        let truncated = &b"*2\r\n:1\r\n"[..];
+        let error = resp_located(truncated).unwrap_err();
+        assert_eq!(error.error, RespError::UnexpectedEof);
+        assert_eq!(error.offset, truncated.len());
+
+        assert_eq!(
+            resp_located(&b"+OK\r\n+rest"[..]),
+            Ok((&b"+rest"[..], Resp::SimpleString(b"OK".to_vec())))
+        );
+    }
+
+    #[test]
+    fn reserves_declared_length() {
+        let (_, parsed) = resp(&b"*3\r\n:1\r\n:2\r\n:3\r\n"[..]).unwrap();
+        if let Resp::Array(Some(values)) = parsed {
+            assert_eq!(values.capacity(), 3);
+        } else {
+            panic!("Error parsing Array");
+        }
+
+        let bogus = &b"*1152921504606846976\r\n:1\r\n"[..];
+        assert_eq!(resp_streaming(bogus), Err(Err::Incomplete(Needed::new(1))));
+    }
+
+    #[test]
+    fn parses_streamed_string() {
+        let input = &b"$?\r\n;4\r\nHell\r\n;6\r\no worl\r\n;1\r\nd\r\n;0\r\n+rest"[..];
+        let (rest, parsed) = resp(input).unwrap();
+        assert_eq!(rest, b"+rest");
+        assert_eq!(parsed, Resp::BulkString(Some(b"Hello world".to_vec())));
+
+        let (_, borrowed) = resp_ref(input).unwrap();
+        assert_eq!(
+            borrowed,
+            RespRef::StreamedString(vec![&b"Hell"[..], &b"o worl"[..], &b"d"[..]])
+        );
+        assert_eq!(try_frame_len(input), Ok(Some(input.len() - rest.len())));
+    }
+
+    #[test]
+    fn fails_on_malformed_string_chunk() {
+        assert_eq!(
+            resp(&b"$?\r\n:4\r\nHell\r\n;0\r\n"[..]),
+            Err(Err::Error(RespError::InvalidChunk))
+        );
+        assert_eq!(
+            resp_streaming(&b"$?\r\n;4\r\nHell\r\n"[..]),
+            Err(Err::Incomplete(Needed::new(1)))
+        );
+        let limits = ParseConfig {
+            max_bulk_len: 8,
+            ..ParseConfig::default()
+        };
+        assert_eq!(
+            resp_with_limits(&b"$?\r\n;4\r\nHell\r\n;6\r\no worl\r\n;0\r\n"[..], &limits),
+            Err(Err::Error(RespError::LimitExceeded))
+        );
+    }
+
+    #[test]
+    fn parses_streamed_aggregates() {
+        let input = &b"*?\r\n:1\r\n~?\r\n#t\r\n.\r\n%?\r\n+a\r\n>0\r\n.\r\n.\r\n"[..];
+        let (rest, parsed) = resp(input).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            parsed,
+            Resp::Array(Some(vec![
+                Resp::Integer(1),
+                Resp::Set(vec![Resp::Boolean(true)]),
+                Resp::Map(vec![(
+                    Resp::SimpleString(b"a".to_vec()),
+                    Resp::Push(vec![])
+                )]),
+            ]))
+        );
+        assert_eq!(
+            resp(&b">?\r\n.\r\n"[..]),
+            Ok((&b""[..], Resp::Push(vec![])))
+        );
+        assert_eq!(
+            resp_streaming(&b"*?\r\n:1\r\n"[..]),
+            Err(Err::Incomplete(Needed::new(1)))
+        );
+    }
+
+    #[test]
+    fn fails_on_misplaced_end_marker() {
+        for input in [
+            &b".\r\n"[..],
+            &b"*1\r\n.\r\n"[..],
+            &b"%?\r\n+a\r\n.\r\n"[..],
+            &b"|?\r\n.\r\n:1\r\n"[..],
+        ] {
+            assert!(resp(input).is_err());
+        }
+        assert_eq!(
+            resp(&b"*1\r\n.\r\n"[..]),
+            Err(Err::Error(RespError::UnknownType(b'.')))
+        );
+    }
+
+    #[test]
+    fn limits_streamed_aggregate_length() {
+        let limits = ParseConfig {
+            max_array_len: 1,
+            ..ParseConfig::default()
+        };
+        assert!(resp_with_limits(&b"*?\r\n:1\r\n.\r\n"[..], &limits).is_ok());
+        assert_eq!(
+            resp_with_limits(&b"*?\r\n:1\r\n:2\r\n.\r\n"[..], &limits),
+            Err(Err::Error(RespError::LimitExceeded))
+        );
+    }
+
+    #[test]
+    fn returns_raw_bytes_of_value() {
+        let input = &b"*2\r\n$3\r\nfoo\r\n:1\r\n*0\r\n"[..];
+        let (rest, (parsed, raw)) = resp_with_raw(input).unwrap();
+        assert_eq!(rest, b"*0\r\n");
+        assert_eq!(raw, &input[..input.len() - 4]);
+        assert_eq!(resp(raw), Ok((&b""[..], parsed)));
+    }
+
+    #[test]
+    fn builds_config() {
+        let config = ParseConfig::new()
+            .max_depth(4)
+            .max_array_len(8)
+            .max_map_len(16)
+            .max_bulk_len(32)
+            .integer_overflow(IntegerOverflow::Saturate)
+            .strict_utf8(true)
+            .lenient_line_endings(true);
+        assert_eq!(
+            config,
+            ParseConfig {
+                max_depth: 4,
+                max_array_len: 8,
+                max_map_len: 16,
+                max_bulk_len: 32,
+                integer_overflow: IntegerOverflow::Saturate,
+                strict_utf8: true,
+                lenient_line_endings: true,
+            }
+        );
+        assert_eq!(
+            resp_with_config(&b"*9\r\n"[..], &config),
+            Err(Err::Error(RespError::LimitExceeded))
+        );
+    }
+
+    #[test]
+    fn reserves_capacity_for_buffered_input_only() {
+        let ctx = Context::new(true, ParseConfig::default());
+        assert_eq!(capacity(100_000_000, 1, b":1\r\n:2\r\n:3", ctx), 3);
+        assert_eq!(capacity(100_000_000, 2, b":1\r\n:2\r\n:3", ctx), 1);
+        assert_eq!(capacity(2, 1, &[0; 64], ctx), 2);
+
+        let limits = ParseConfig {
+            max_array_len: 1000,
+            ..ParseConfig::default()
+        };
+        let ctx = Context::new(true, limits);
+        assert_eq!(
+            value::<Resp>(b"*100000000\r\n", ctx),
+            Err(Err::Error(RespError::LimitExceeded))
+        );
+    }
+
+    #[test]
+    fn decodes_complete_frames_only() {
+        let input = &b"*1\r\n:1\r\n+OK\r\n*2\r\n:1\r\n"[..];
+        let (parsed, rest) = decode_frames(input).unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Resp::Array(Some(vec![Resp::Integer(1)])),
+                Resp::SimpleString(b"OK".to_vec())
+            ]
+        );
+        assert_eq!(rest, b"*2\r\n:1\r\n");
+
+        assert_eq!(decode_frames(&b""[..]), Ok((vec![], &b""[..])));
+        assert_eq!(
+            decode_frames(&b"+OK\r\n?\r\n"[..]),
+            Err(RespError::UnknownType(b'?'))
+        );
+    }
+
+    #[test]
+    fn handles_empty_and_lone_carriage_return_input() {
+        assert_eq!(resp(b""), Err(Err::Error(RespError::UnexpectedEof)));
+        assert_eq!(resp_streaming(b""), Err(Err::Incomplete(Needed::new(1))));
+        assert_eq!(resp(b"\r"), Err(Err::Error(RespError::UnknownType(b'\r'))));
+        assert_eq!(
+            resp_streaming(b"\r"),
+            Err(Err::Error(RespError::UnknownType(b'\r')))
+        );
+        assert_eq!(try_frame_len(b""), Ok(None));
+        assert_eq!(try_frame_len(b"\r"), Err(RespError::UnknownType(b'\r')));
+
+        assert_eq!(resp(b"+OK\r"), Err(Err::Error(RespError::MissingCrlf)));
+        assert_eq!(
+            resp_streaming(b"+OK\r"),
+            Err(Err::Incomplete(Needed::new(1)))
+        );
+        assert_eq!(
+            resp_streaming(b"$2\r\nOK\r"),
+            Err(Err::Incomplete(Needed::new(1)))
+        );
+    }
+
+    #[test]
+    fn limits_aggregate_length() {
+        let limits = ParseConfig {
+            max_array_len: 2,
+            max_map_len: 1,
+            ..ParseConfig::default()
+        };
+        assert!(resp_with_limits(&b"*2\r\n:1\r\n:2\r\n"[..], &limits).is_ok());
+        assert!(resp_with_limits(&b"%1\r\n:1\r\n:2\r\n"[..], &limits).is_ok());
+
+        for oversized_input in [
+            &b"*1000000000\r\n"[..],
+            &b"~3\r\n"[..],
+            &b">3\r\n"[..],
+            &b"%2\r\n"[..],
+        ] {
+            if let Err(Err::Error(error)) = resp_with_limits(oversized_input, &limits) {
+                assert_eq!(error, RespError::LimitExceeded);
+            } else {
+                panic!("Error limiting aggregate length");
+            }
+        }
+    }
+
+    #[test]
+    fn limits_bulk_length() {
+        let limits = ParseConfig {
+            max_bulk_len: 4,
+            ..ParseConfig::default()
+        };
+        assert!(resp_with_limits(&b"$4\r\ngood\r\n"[..], &limits).is_ok());
+        assert!(resp_with_limits(&b"$-1\r\n"[..], &limits).is_ok());
+
+        for oversized_input in [
+            &b"$4294967295\r\n"[..],
+            &b"!5\r\nERROR\r\n"[..],
+            &b"=15\r\ntxt:Some string\r\n"[..],
+        ] {
+            if let Err(Err::Error(error)) = resp_with_limits(oversized_input, &limits) {
+                assert_eq!(error, RespError::LimitExceeded);
+            } else {
+                panic!("Error limiting bulk length");
+            }
+        }
+    }
+
+    #[test]
+    fn parses_n_values() {
+        let input = &b"+OK\r\n+QUEUED\r\n*1\r\n:1\r\n"[..];
+        let (rest, parsed) = parse_n(input, 3).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(parsed.len(), 3);
+
+        let (rest, parsed) = parse_n(input, 0).unwrap();
+        assert_eq!(rest, input);
+        assert!(parsed.is_empty());
+
+        assert!(parse_n(input, 4).is_err());
+    }
+
+    #[test]
+    fn parses_exactly_one_value() {
+        assert_eq!(
+            parse_exact(&b"*1\r\n$2\r\nOK\r\n"[..]),
+            Ok(Resp::Array(Some(vec![Resp::BulkString(Some(
+                b"OK".to_vec()
+            ))])))
+        );
+        assert_eq!(
+            parse_exact(&b"+OK\r\n\r\n"[..]),
+            Err(RespError::TrailingBytes)
+        );
+        assert_eq!(parse_exact(&b"$2\r\nO"[..]), Err(RespError::UnexpectedEof));
+        assert_eq!(
+            parse_exact(&b"?\r\n"[..]),
+            Err(RespError::UnknownType(b'?'))
+        );
+    }
+
+    #[test]
+    fn reports_exact_needed_size_of_payloads() {
+        let mut input = b"$1000\r\n".to_vec();
+        input.extend_from_slice(&[b'x'; 10]);
+        assert_eq!(
+            resp_streaming(&input),
+            Err(Err::Incomplete(Needed::new(992)))
+        );
+        input.extend_from_slice(&[b'x'; 990]);
+        assert_eq!(resp_streaming(&input), Err(Err::Incomplete(Needed::new(2))));
+        input.push(b'\r');
+        assert_eq!(resp_streaming(&input), Err(Err::Incomplete(Needed::new(1))));
+        input.push(b'\n');
+        assert!(resp_streaming(&input).is_ok());
+
+        assert_eq!(
+            resp_streaming(&b"!5\r\nER"[..]),
+            Err(Err::Incomplete(Needed::new(5)))
+        );
+        assert_eq!(
+            resp_streaming(&b"=15\r\ntxt:"[..]),
+            Err(Err::Incomplete(Needed::new(13)))
+        );
+    }
+
+    #[test]
+    fn measures_frames() {
+        let input = &b"%1\r\n+a\r\n*2\r\n:1\r\n$-1\r\n=15\r\ntxt:Some string\r\n"[..];
+        assert_eq!(try_frame_len(input), Ok(Some(21)));
+        assert_eq!(try_frame_len(&input[21..]), Ok(Some(22)));
+        for end in 0..21 {
+            assert_eq!(try_frame_len(&input[..end]), Ok(None));
+        }
+        assert_eq!(
+            try_frame_len(&b"*1\r\n?\r\n"[..]),
+            Err(RespError::UnknownType(b'?'))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_with_serde() {
+        use serde_test::{assert_tokens, Token};
+
+        let (_, parsed) = resp(&b"*2\r\n+OK\r\n%1\r\n$-1\r\n#t\r\n"[..]).unwrap();
+        assert_tokens(
+            &parsed,
+            &[
+                Token::NewtypeVariant {
+                    name: "Resp",
+                    variant: "Array",
+                },
+                Token::Some,
+                Token::Seq { len: Some(2) },
+                Token::NewtypeVariant {
+                    name: "Resp",
+                    variant: "SimpleString",
+                },
+                Token::Seq { len: Some(2) },
+                Token::U8(b'O'),
+                Token::U8(b'K'),
+                Token::SeqEnd,
+                Token::NewtypeVariant {
+                    name: "Resp",
+                    variant: "Map",
+                },
+                Token::Seq { len: Some(1) },
+                Token::Tuple { len: 2 },
+                Token::NewtypeVariant {
+                    name: "Resp",
+                    variant: "BulkString",
+                },
+                Token::None,
+                Token::NewtypeVariant {
+                    name: "Resp",
+                    variant: "Boolean",
+                },
+                Token::Bool(true),
+                Token::TupleEnd,
+                Token::SeqEnd,
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_all() {
+        let input = &b"$4\r\ngood\r\n:8\r\n+OK\r\n"[..];
+        let (input, parsed) = parse_all(input).unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Resp::BulkString(Some(b"good".to_vec())),
+                Resp::Integer(8),
+                Resp::SimpleString(b"OK".to_vec())
+            ]
+        );
+        assert!(input.is_empty());
+
+        let (_, parsed) = parse_all(&b""[..]).unwrap();
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_on_corrupted_trailing_value() {
+        let corrupted_input = &b"+OK\r\n:8\r\n$4\r\nbad\r\n"[..];
+        parse_all(corrupted_input).unwrap();
+    }
 
     #[test]
     fn parses_multiple() {
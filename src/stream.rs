@@ -0,0 +1,495 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, Read};
+
+use nom::{Err, Needed};
+
+use crate::{parser::frame_len, resp_with_config, ParseConfig, Resp, RespError};
+
+/// Stateful RESP parser buffering partial input across reads.
+/// Bytes are appended with [`Parser::feed`] and complete values are taken out as soon as they are available.
+///
+/// # Examples
+/// ```
+/// use respirator::{Parser, Resp};
+///
+/// let mut parser = Parser::new();
+/// parser.feed(b"$4\r\nRe");
+/// assert_eq!(parser.next(), None);
+/// parser.feed(b"sp\r\n:8\r\n");
+/// assert_eq!(parser.next(), Some(Resp::BulkString(Some(b"Resp".to_vec()))));
+/// assert_eq!(parser.next(), Some(Resp::Integer(8)));
+/// assert_eq!(parser.next(), None);
+/// ```
+#[derive(Debug)]
+pub struct Parser {
+    buffer: Vec<u8>,
+    position: usize,
+    config: ParseConfig,
+    framer: Framer,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser {
+    /// Creates parser with an empty buffer.
+    pub fn new() -> Self {
+        Self::with_config(ParseConfig::default())
+    }
+
+    /// Creates parser with an empty buffer, parsing according to the given [`ParseConfig`],
+    /// e.g. to limit how much memory a peer may make it allocate.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - options of the parser
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::{ParseConfig, Parser, RespError};
+    ///
+    /// let mut parser = Parser::with_config(ParseConfig::new().max_bulk_len(4));
+    /// parser.feed(b"$5\r\n");
+    /// assert_eq!(parser.try_next(), Err(RespError::LimitExceeded));
+    /// ```
+    pub fn with_config(config: ParseConfig) -> Self {
+        Parser {
+            buffer: Vec::new(),
+            position: 0,
+            config,
+            framer: Framer::default(),
+        }
+    }
+
+    /// Appends bytes to the internal buffer, discarding bytes of already parsed values.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - a byte slice, e.g. freshly read from a socket
+    pub fn feed(&mut self, bytes: &[u8]) {
+        if self.position > 0 {
+            self.buffer.drain(..self.position);
+            self.position = 0;
+        }
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Parses next value from the buffer.
+    /// Returns `Ok(None)` if the buffer does not contain a complete value yet
+    /// and `Err` with [`RespError`] if buffered bytes are not valid RESP.
+    /// Malformed bytes are kept, so the error is returned again on every call until [`Parser::clear`] discards them.
+    pub fn try_next(&mut self) -> Result<Option<Resp>, RespError> {
+        match self
+            .framer
+            .next(&self.buffer[self.position..], &self.config)?
+        {
+            Some((value, len)) => {
+                self.position += len;
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns bytes that are buffered but not yet parsed.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[self.position..]
+    }
+
+    /// Discards all buffered bytes, e.g. to recover after malformed input, once the peer is known to start afresh.
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::{Parser, Resp, RespError};
+    ///
+    /// let mut parser = Parser::new();
+    /// parser.feed(b"?\r\n");
+    /// assert_eq!(parser.try_next(), Err(RespError::UnknownType(b'?')));
+    /// parser.clear();
+    /// parser.feed(b":8\r\n");
+    /// assert_eq!(parser.try_next(), Ok(Some(Resp::Integer(8))));
+    /// ```
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.position = 0;
+        self.framer = Framer::default();
+    }
+}
+
+/// Tracks how many bytes a partial value needs at least, so that readers probe buffered bytes again
+/// only once they may have arrived, and build the value only once it's complete,
+/// rather than re-parsing the partial value into a tree on every read.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Framer {
+    wanted: usize,
+}
+
+impl Framer {
+    /// Parses value at the head of `buffered` once it's complete, returning it with the number of bytes it takes.
+    pub(crate) fn next(
+        &mut self,
+        buffered: &[u8],
+        config: &ParseConfig,
+    ) -> Result<Option<(Resp, usize)>, RespError> {
+        if buffered.len() < self.wanted {
+            return Ok(None);
+        }
+        let len = match frame_len(buffered, config) {
+            Ok(len) => len,
+            Err(Err::Incomplete(needed)) => {
+                let needed = match needed {
+                    Needed::Size(needed) => needed.get(),
+                    Needed::Unknown => 1,
+                };
+                self.wanted = buffered.len().saturating_add(needed);
+                return Ok(None);
+            }
+            Err(Err::Error(error)) | Err(Err::Failure(error)) => return Err(error),
+        };
+        self.wanted = 0;
+        match resp_with_config(&buffered[..len], config) {
+            Ok((_, value)) => Ok(Some((value, len))),
+            Err(Err::Incomplete(_)) => Err(RespError::UnexpectedEof),
+            Err(Err::Error(error)) | Err(Err::Failure(error)) => Err(error),
+        }
+    }
+}
+
+/// Yields complete values from the buffer, stops when more data is needed or buffered bytes are malformed.
+/// Errors are swallowed, i.e. malformed input looks like the end of buffered values,
+/// use [`Parser::try_next`] to tell the two apart.
+impl Iterator for Parser {
+    type Item = Resp;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().ok().flatten()
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) const READ_CHUNK_SIZE: usize = 4096;
+
+/// Adapter reading RESP values from any `std::io::Read`, e.g. `TcpStream`.
+///
+/// # Examples
+/// ```
+/// use respirator::{Resp, RespReader};
+///
+/// let mut reader = RespReader::new(&b"+OK\r\n:8\r\n"[..]);
+/// assert_eq!(reader.read_value().unwrap(), Resp::SimpleString(b"OK".to_vec()));
+/// assert_eq!(reader.read_value().unwrap(), Resp::Integer(8));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct RespReader<R> {
+    reader: R,
+    parser: Parser,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> RespReader<R> {
+    /// Creates adapter reading from given reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - a reader to read RESP values from
+    pub fn new(reader: R) -> Self {
+        Self::with_config(reader, ParseConfig::default())
+    }
+
+    /// Creates adapter reading from given reader, parsing according to the given [`ParseConfig`].
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - a reader to read RESP values from
+    /// * `config` - options of the parser
+    pub fn with_config(reader: R, config: ParseConfig) -> Self {
+        RespReader {
+            reader,
+            parser: Parser::with_config(config),
+        }
+    }
+
+    /// Reads next value, pulling from the underlying reader until a complete value is buffered.
+    /// Fails with `UnexpectedEof` if the reader ends before a complete value
+    /// and with `InvalidData` if read bytes are not valid RESP.
+    pub fn read_value(&mut self) -> io::Result<Resp> {
+        let mut chunk = [0; READ_CHUNK_SIZE];
+        loop {
+            match self.parser.try_next() {
+                Ok(Some(value)) => return Ok(value),
+                Ok(None) => {}
+                Err(kind) => return Err(invalid_data(kind)),
+            }
+            let read = match self.reader.read(&mut chunk) {
+                Ok(read) => read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            if read == 0 {
+                return Err(unexpected_eof());
+            }
+            self.parser.feed(&chunk[..read]);
+        }
+    }
+
+    /// Returns the underlying reader, discarding buffered bytes.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+/// Reads exactly one value from a buffered reader, e.g. a file or a pipe, consuming no bytes past its end,
+/// so the reader can be used again for the next value or any other data.
+/// Fails with `UnexpectedEof` if the reader ends before a complete value
+/// and with `InvalidData`, whose source is the `RespError`, if read bytes are not valid RESP.
+///
+/// # Arguments
+///
+/// * `reader` - a buffered reader to read RESP value from
+///
+/// # Examples
+/// ```
+/// use std::io::{BufRead, BufReader};
+/// use respirator::{from_bufread, Resp};
+///
+/// let mut reader = BufReader::with_capacity(4, &b"$11\r\nhello world\r\ntrailing log line\n"[..]);
+/// assert_eq!(from_bufread(&mut reader).unwrap(), Resp::BulkString(Some(b"hello world".to_vec())));
+/// let mut line = String::new();
+/// reader.read_line(&mut line).unwrap();
+/// assert_eq!(line, "trailing log line\n");
+/// ```
+#[cfg(feature = "std")]
+pub fn from_bufread<R: BufRead>(reader: &mut R) -> io::Result<Resp> {
+    from_bufread_with_config(reader, &ParseConfig::default())
+}
+
+/// Variant of [`from_bufread`] parsing according to the given [`ParseConfig`].
+///
+/// # Arguments
+///
+/// * `reader` - a buffered reader to read RESP value from
+/// * `config` - options of the parser
+///
+/// # Examples
+/// ```
+/// use respirator::{from_bufread_with_config, ParseConfig};
+///
+/// let config = ParseConfig::new().max_depth(1);
+/// let error = from_bufread_with_config(&mut &b"*1\r\n*1\r\n:1\r\n"[..], &config).unwrap_err();
+/// assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+/// ```
+#[cfg(feature = "std")]
+pub fn from_bufread_with_config<R: BufRead>(
+    reader: &mut R,
+    config: &ParseConfig,
+) -> io::Result<Resp> {
+    let mut buf = Vec::new();
+    let mut framer = Framer::default();
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        if available.is_empty() {
+            return Err(unexpected_eof());
+        }
+        let buffered = buf.len();
+        buf.extend_from_slice(available);
+        match framer.next(&buf, config) {
+            Ok(Some((value, len))) => {
+                reader.consume(len - buffered);
+                return Ok(value);
+            }
+            Ok(None) => {
+                let read = buf.len() - buffered;
+                reader.consume(read);
+            }
+            Err(error) => return Err(invalid_data(error)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn invalid_data(error: RespError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn unexpected_eof() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "stream ended before a complete RESP value",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bulk_string_fed_in_three_parts() {
+        let mut parser = Parser::new();
+        parser.feed(b"$11\r\nhel");
+        assert_eq!(parser.next(), None);
+        parser.feed(b"lo wor");
+        assert_eq!(parser.next(), None);
+        parser.feed(b"ld\r\n");
+        assert_eq!(
+            parser.next(),
+            Some(Resp::BulkString(Some(b"hello world".to_vec())))
+        );
+        assert!(parser.buffered().is_empty());
+    }
+
+    #[test]
+    fn waits_on_empty_and_partial_line_ending_reads() {
+        let mut parser = Parser::new();
+        assert_eq!(parser.try_next(), Ok(None));
+        parser.feed(b"");
+        assert_eq!(parser.try_next(), Ok(None));
+        parser.feed(b"+OK\r");
+        assert_eq!(parser.try_next(), Ok(None));
+        parser.feed(b"\n");
+        assert_eq!(
+            parser.try_next(),
+            Ok(Some(Resp::SimpleString(b"OK".to_vec())))
+        );
+
+        parser.feed(b"\r");
+        assert_eq!(parser.try_next(), Err(RespError::UnknownType(b'\r')));
+    }
+
+    #[test]
+    fn yields_all_buffered_values() {
+        let mut parser = Parser::new();
+        parser.feed(b"+OK\r\n:1\r\n*1\r\n");
+        assert_eq!(
+            parser.by_ref().collect::<Vec<_>>(),
+            vec![Resp::SimpleString(b"OK".to_vec()), Resp::Integer(1)]
+        );
+        assert_eq!(parser.buffered(), b"*1\r\n");
+        parser.feed(b"_\r\n");
+        assert_eq!(parser.next(), Some(Resp::Array(Some(vec![Resp::Null]))));
+    }
+
+    #[test]
+    fn parses_values_fed_byte_by_byte() {
+        let input = &b"*2\r\n$10\r\n0123456789\r\n%1\r\n+k\r\n:1\r\n:2\r\n"[..];
+        let mut parser = Parser::new();
+        let mut parsed = Vec::new();
+        for byte in input {
+            parser.feed(&[*byte]);
+            parsed.extend(parser.by_ref());
+        }
+        let (_, expected) = crate::parse_n(input, 2).unwrap();
+        assert_eq!(parsed, expected);
+        assert!(parser.buffered().is_empty());
+    }
+
+    #[test]
+    fn reports_malformed_input() {
+        let mut parser = Parser::new();
+        parser.feed(b"?\r\n");
+        assert_eq!(parser.try_next(), Err(RespError::UnknownType(b'?')));
+        assert_eq!(parser.next(), None);
+        parser.feed(b":1\r\n");
+        assert_eq!(parser.try_next(), Err(RespError::UnknownType(b'?')));
+
+        parser.clear();
+        assert!(parser.buffered().is_empty());
+        parser.feed(b":1\r\n");
+        assert_eq!(parser.next(), Some(Resp::Integer(1)));
+    }
+
+    /// Reader returning its chunks one by one, as a socket receiving separate packets would.
+    struct ChunkedReader(Vec<&'static [u8]>);
+
+    impl Read for ChunkedReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                return Ok(0);
+            }
+            let chunk = self.0.remove(0);
+            buf[..chunk.len()].copy_from_slice(chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    #[test]
+    fn reads_bulk_string_split_across_reads() {
+        let mut reader =
+            RespReader::new(ChunkedReader(vec![b"$11\r\nhel", b"lo wor", b"ld\r\n:1"]));
+        assert_eq!(
+            reader.read_value().unwrap(),
+            Resp::BulkString(Some(b"hello world".to_vec()))
+        );
+        assert_eq!(
+            reader.read_value().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn fails_reading_malformed_stream() {
+        let mut reader = RespReader::new(&b"$4\r\nbad\r\n"[..]);
+        assert_eq!(
+            reader.read_value().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn reads_according_to_config() {
+        let config = ParseConfig::new().lenient_line_endings(true);
+        let mut reader = RespReader::with_config(&b"+OK\n"[..], config);
+        assert_eq!(
+            reader.read_value().unwrap(),
+            Resp::SimpleString(b"OK".to_vec())
+        );
+
+        let config = ParseConfig::new().max_array_len(1);
+        let error = RespReader::with_config(&b"*2\r\n"[..], config)
+            .read_value()
+            .unwrap_err();
+        let source = error.into_inner().unwrap();
+        assert_eq!(
+            source.downcast_ref::<RespError>(),
+            Some(&RespError::LimitExceeded)
+        );
+    }
+
+    #[test]
+    fn reads_one_value_from_buffered_reader() {
+        let input = &b"*2\r\n$5\r\nhello\r\n:1\r\n+next\r\n"[..];
+        let mut reader = io::BufReader::with_capacity(3, input);
+        assert_eq!(
+            from_bufread(&mut reader).unwrap(),
+            Resp::Array(Some(vec!["hello".into(), Resp::Integer(1)]))
+        );
+        assert_eq!(
+            from_bufread(&mut reader).unwrap(),
+            Resp::SimpleString(b"next".to_vec())
+        );
+        assert_eq!(
+            from_bufread(&mut reader).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn fails_reading_malformed_value_from_buffered_reader() {
+        let error = from_bufread(&mut &b"$4\r\nbad\r\n"[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let source = error.into_inner().unwrap();
+        assert_eq!(
+            source.downcast_ref::<RespError>(),
+            Some(&RespError::MissingCrlf)
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Parsers of individual RESP types, for callers which have already consumed the type byte,
+//! e.g. a command dispatcher which read it while framing.
+//! Each of them expects input starting right after the type byte and enforces limits of the default [`ParseConfig`].
+
+use nom::IResult;
+
+use crate::{parser, parser::Context, ParseConfig, Resp, RespError};
+
+fn context() -> Context {
+    Context::new(false, ParseConfig::default())
+}
+
+/// Parses Simple String following its `+` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::simple_string(&b"OK\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::SimpleString(b"OK".to_vec()));
+/// ```
+pub fn simple_string(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::simple_string(input, context())
+}
+
+/// Parses Integer following its `:` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::integer(&b"-8\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::Integer(-8));
+/// ```
+pub fn integer(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::integer(input, context())
+}
+
+/// Parses Error following its `-` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::error(&b"ERR unknown command\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::Error(b"ERR unknown command".to_vec()));
+/// ```
+pub fn error(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::error(input, context())
+}
+
+/// Parses Bulk String following its `$` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::bulk_string(&b"4\r\nResp\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::BulkString(Some(b"Resp".to_vec())));
+/// ```
+pub fn bulk_string(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::bulk_string(input, context())
+}
+
+/// Parses Array following its `*` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::array(&b"1\r\n:8\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::Array(Some(vec![Resp::Integer(8)])));
+/// ```
+pub fn array(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::array(input, context())
+}
+
+/// Parses Double following its `,` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::double(&b"2.5\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::Double(2.5));
+/// ```
+pub fn double(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::double(input, context())
+}
+
+/// Parses Boolean following its `#` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::boolean(&b"t\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::Boolean(true));
+/// ```
+pub fn boolean(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::boolean(input, context())
+}
+
+/// Parses Null following its `_` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::null(&b"\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::Null);
+/// ```
+pub fn null(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::null(input, context())
+}
+
+/// Parses Map following its `%` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::map(&b"1\r\n+key\r\n:1\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::Map(vec![(Resp::SimpleString(b"key".to_vec()), Resp::Integer(1))]));
+/// ```
+pub fn map(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::map(input, context())
+}
+
+/// Parses Set following its `~` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::set(&b"1\r\n:1\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::Set(vec![Resp::Integer(1)]));
+/// ```
+pub fn set(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::set(input, context())
+}
+
+/// Parses Push following its `>` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::push(&b"1\r\n:1\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::Push(vec![Resp::Integer(1)]));
+/// ```
+pub fn push(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::push(input, context())
+}
+
+/// Parses Big Number following its `(` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::big_number(&b"-12345678901234567890\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::BigNumber(b"-12345678901234567890".to_vec()));
+/// ```
+pub fn big_number(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::big_number(input, context())
+}
+
+/// Parses Verbatim String following its `=` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::verbatim_string(&b"7\r\ntxt:abc\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::VerbatimString { format: *b"txt", data: b"abc".to_vec() });
+/// ```
+pub fn verbatim_string(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::verbatim_string(input, context())
+}
+
+/// Parses Bulk Error following its `!` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::bulk_error(&b"3\r\nERR\r\n"[..]).unwrap();
+/// assert_eq!(parsed, Resp::BulkError(b"ERR".to_vec()));
+/// ```
+pub fn bulk_error(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::bulk_error(input, context())
+}
+
+/// Parses Attribute following its `|` type byte.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed, without the type byte
+///
+/// # Examples
+/// ```
+/// use respirator::{types, Resp};
+///
+/// let (_, parsed) = types::attribute(&b"1\r\n+ttl\r\n:1\r\n+OK\r\n"[..]).unwrap();
+/// assert_eq!(
+///     parsed,
+///     Resp::Attribute {
+///         attributes: vec![(Resp::SimpleString(b"ttl".to_vec()), Resp::Integer(1))],
+///         value: Box::new(Resp::SimpleString(b"OK".to_vec())),
+///     }
+/// );
+/// ```
+pub fn attribute(input: &[u8]) -> IResult<&[u8], Resp, RespError> {
+    parser::attribute(input, context())
+}
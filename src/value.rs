@@ -0,0 +1,932 @@
+use alloc::vec::{self, Vec};
+use core::{
+    hash::{Hash, Hasher},
+    mem, slice,
+};
+
+use crate::{parser::parse_double, Resp};
+
+impl Resp {
+    /// Returns value of `Integer`, None for other variants.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b":8\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.as_integer(), Some(8));
+    /// ```
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Resp::Integer(val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns value of `Double`, or of `BulkString` and `SimpleString` holding a floating point number
+    /// (as RESP2 servers reply with e.g. sorted set scores), None for other variants or payloads.
+    /// Payloads may be `inf`, `-inf` and `nan`, like the ones of `Double`.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"$4\r\n1.25\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.as_f64(), Some(1.25));
+    /// let (_, parsed) = respirator::resp(&b",-inf\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.as_f64(), Some(f64::NEG_INFINITY));
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Resp::Double(val) => Some(*val),
+            Resp::BulkString(Some(val)) | Resp::SimpleString(val) => parse_double(val),
+            _ => None,
+        }
+    }
+
+    /// Returns payload of non-null `BulkString`, None for other variants.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"$3\r\nstr\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.as_bulk_string(), Some(&b"str"[..]));
+    /// ```
+    pub fn as_bulk_string(&self) -> Option<&[u8]> {
+        match self {
+            Resp::BulkString(Some(val)) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns payload of `SimpleString`, None for other variants.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"+OK\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.as_simple_string(), Some(&b"OK"[..]));
+    /// ```
+    pub fn as_simple_string(&self) -> Option<&[u8]> {
+        match self {
+            Resp::SimpleString(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns elements of non-null `Array`, None for other variants.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"*1\r\n:8\r\n"[..]).unwrap();
+    /// let first = parsed.as_array().and_then(|values| values.first()).and_then(|value| value.as_integer());
+    /// assert_eq!(first, Some(8));
+    /// ```
+    pub fn as_array(&self) -> Option<&[Resp]> {
+        match self {
+            Resp::Array(Some(values)) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Looks up value of the first entry of a `Map`, or of an `Array` of alternating keys and values as returned by RESP2
+    /// servers (e.g. to `CONFIG GET`), whose key is `SimpleString` or `BulkString` with given payload.
+    /// Returns None if there is no such entry or for other variants, including `Array` of odd length.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - payload of the key to look for
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"%1\r\n+role\r\n$6\r\nmaster\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.map_get(b"role").and_then(|role| role.as_str()), Some("master"));
+    ///
+    /// let (_, parsed) = respirator::resp(&b"*2\r\n$7\r\ntimeout\r\n$1\r\n0\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.map_get(b"timeout").and_then(|timeout| timeout.as_str()), Some("0"));
+    /// ```
+    pub fn map_get(&self, key: &[u8]) -> Option<&Resp> {
+        match self {
+            Resp::Map(entries) => entries
+                .iter()
+                .find(|(candidate, _)| is_key(candidate, key))
+                .map(|(_, value)| value),
+            Resp::Array(Some(values)) if values.len() % 2 == 0 => values
+                .chunks_exact(2)
+                .find(|entry| is_key(&entry[0], key))
+                .map(|entry| &entry[1]),
+            _ => None,
+        }
+    }
+
+    /// Returns protocol version from a reply to `HELLO`, i.e. the `proto` entry of a map, None if it has none.
+    ///
+    /// # Examples
+    /// ```
+    /// let input = &b"%2\r\n$6\r\nserver\r\n$5\r\nredis\r\n$5\r\nproto\r\n:3\r\n"[..];
+    /// let (_, parsed) = respirator::resp(input).unwrap();
+    /// assert_eq!(parsed.hello_proto(), Some(3));
+    /// ```
+    pub fn hello_proto(&self) -> Option<i64> {
+        self.map_get(b"proto")?.as_integer()
+    }
+
+    /// Returns message of `Error` or `BulkError`, None for other variants.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"-ERR unknown command\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.as_error(), Some(&b"ERR unknown command"[..]));
+    /// ```
+    pub fn as_error(&self) -> Option<&[u8]> {
+        match self {
+            Resp::Error(val) | Resp::BulkError(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns payload of `SimpleString`, non-null `BulkString`, `Error` or `BulkError` as text,
+    /// None for other variants or if the payload is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"$5\r\nhello\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.as_str(), Some("hello"));
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        text(self).and_then(|val| core::str::from_utf8(val).ok())
+    }
+
+    /// Returns payload length of `SimpleString`, non-null `BulkString`, `Error`, `BulkError` or `VerbatimString`,
+    /// the latter not counting its format prefix, None for other variants.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"=15\r\ntxt:Some string\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.byte_len(), Some(11));
+    /// ```
+    pub fn byte_len(&self) -> Option<usize> {
+        match self {
+            Resp::VerbatimString { data, .. } => Some(data.len()),
+            value => text(value).map(<[u8]>::len),
+        }
+    }
+
+    /// Returns code of `Error` or `BulkError`, i.e. its payload up to the first space (e.g. `WRONGTYPE` or `MOVED`),
+    /// None for other variants.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"-MOVED 3999 127.0.0.1:6381\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.error_code(), Some(&b"MOVED"[..]));
+    /// ```
+    pub fn error_code(&self) -> Option<&[u8]> {
+        self.as_error().map(|val| split_error(val).0)
+    }
+
+    /// Returns message of `Error` or `BulkError` following its code and a space,
+    /// empty if the payload is the code alone, None for other variants.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"-MOVED 3999 127.0.0.1:6381\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.error_message(), Some(&b"3999 127.0.0.1:6381"[..]));
+    /// ```
+    pub fn error_message(&self) -> Option<&[u8]> {
+        self.as_error().map(|val| split_error(val).1)
+    }
+
+    /// Splits `Error` or `BulkError` into its code and message like [`error_code`](Resp::error_code)
+    /// and [`error_message`](Resp::error_message), but reports the code only if the first word is
+    /// all-uppercase ASCII (e.g. `ERR` or `WRONGTYPE`), otherwise the whole payload is a free-form message.
+    /// Returns None for other variants.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"-ERR unknown command\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.error_parts(), Some((Some(&b"ERR"[..]), &b"unknown command"[..])));
+    ///
+    /// let (_, parsed) = respirator::resp(&b"-Operation failed\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.error_parts(), Some((None, &b"Operation failed"[..])));
+    /// ```
+    pub fn error_parts(&self) -> Option<(Option<&[u8]>, &[u8])> {
+        let val = self.as_error()?;
+        let (code, message) = split_error(val);
+        if !code.is_empty() && code.iter().all(u8::is_ascii_uppercase) {
+            Some((Some(code), message))
+        } else {
+            Some((None, val))
+        }
+    }
+
+    /// Returns format hint of `VerbatimString`, e.g. `txt` or `mkd`,
+    /// None for other variants or if the format is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"=15\r\ntxt:Some string\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.verbatim_format(), Some("txt"));
+    /// ```
+    pub fn verbatim_format(&self) -> Option<&str> {
+        match self {
+            Resp::VerbatimString { format, .. } => core::str::from_utf8(format).ok(),
+            _ => None,
+        }
+    }
+
+    /// Checks whether value is `VerbatimString` in markdown (`mkd`) format.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"=8\r\nmkd:# Hi\r\n"[..]).unwrap();
+    /// assert!(parsed.verbatim_is_markdown());
+    /// ```
+    pub fn verbatim_is_markdown(&self) -> bool {
+        matches!(self, Resp::VerbatimString { format, .. } if format == b"mkd")
+    }
+
+    /// Checks whether value is `Error` or `BulkError`.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"-ERR\r\n"[..]).unwrap();
+    /// assert!(parsed.is_error());
+    /// ```
+    pub fn is_error(&self) -> bool {
+        matches!(self, Resp::Error(_) | Resp::BulkError(_))
+    }
+
+    /// Checks whether value is `+OK` status reply.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"+OK\r\n"[..]).unwrap();
+    /// assert!(parsed.is_ok());
+    /// ```
+    pub fn is_ok(&self) -> bool {
+        self.is_status(b"OK")
+    }
+
+    /// Checks whether value is `+QUEUED` status reply, sent for commands issued inside `MULTI`.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"+QUEUED\r\n"[..]).unwrap();
+    /// assert!(parsed.is_queued());
+    /// ```
+    pub fn is_queued(&self) -> bool {
+        self.is_status(b"QUEUED")
+    }
+
+    /// Checks whether value is `+PONG` status reply.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"+PONG\r\n"[..]).unwrap();
+    /// assert!(parsed.is_pong());
+    /// ```
+    pub fn is_pong(&self) -> bool {
+        self.is_status(b"PONG")
+    }
+
+    fn is_status(&self, status: &[u8]) -> bool {
+        matches!(self, Resp::SimpleString(val) if val == status)
+    }
+
+    /// Checks whether value is null, i.e. null `BulkString`, null `Array` or RESP3 `Null`.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"$-1\r\n"[..]).unwrap();
+    /// assert!(parsed.is_null());
+    /// ```
+    pub fn is_null(&self) -> bool {
+        matches!(
+            self,
+            Resp::BulkString(None) | Resp::Array(None) | Resp::Null
+        )
+    }
+
+    /// Checks whether value is an aggregate, i.e. non-null `Array`, `Map`, `Set` or `Push`.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"*1\r\n:8\r\n"[..]).unwrap();
+    /// assert!(parsed.is_aggregate());
+    /// ```
+    pub fn is_aggregate(&self) -> bool {
+        matches!(
+            self,
+            Resp::Array(Some(_)) | Resp::Map(_) | Resp::Set(_) | Resp::Push(_)
+        )
+    }
+
+    /// Moves elements out of non-null `Array`, `Set` or `Push`, None for other variants.
+    /// Unlike [`as_array`](Resp::as_array), consumes the value, so elements can be handed over without cloning.
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// let (_, parsed) = respirator::resp(&b"~2\r\n:1\r\n:2\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.take_array(), Some(vec![Resp::Integer(1), Resp::Integer(2)]));
+    /// ```
+    pub fn take_array(self) -> Option<Vec<Resp>> {
+        match self {
+            Resp::Array(Some(values)) | Resp::Set(values) | Resp::Push(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Flattens nested `Array`, `Set` and `Push` values into their leaves, in order.
+    /// Null and empty aggregates contribute nothing, other values (including `Map`) are leaves themselves.
+    ///
+    /// # Examples
+    /// ```
+    /// use respirator::Resp;
+    ///
+    /// let (_, parsed) = respirator::resp(&b"*3\r\n*2\r\n:1\r\n:2\r\n*-1\r\n*1\r\n:3\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.flatten(), vec![Resp::Integer(1), Resp::Integer(2), Resp::Integer(3)]);
+    /// ```
+    pub fn flatten(self) -> Vec<Resp> {
+        let mut leaves = Vec::new();
+        self.flatten_into(&mut leaves);
+        leaves
+    }
+
+    fn flatten_into(self, leaves: &mut Vec<Resp>) {
+        match self {
+            Resp::Array(Some(values)) | Resp::Set(values) | Resp::Push(values) => {
+                for value in values {
+                    value.flatten_into(leaves);
+                }
+            }
+            Resp::Array(None) => {}
+            leaf => leaves.push(leaf),
+        }
+    }
+
+    /// Counts nodes of the value, i.e. the value itself and all its elements, recursively.
+    /// Map and Attribute entries count as two nodes, one for the key and one for the value.
+    /// Together with [`Resp::encoded_len`] it measures complexity of a reply.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"*2\r\n:1\r\n%1\r\n+a\r\n:2\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.node_count(), 5);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        let children: usize = match self {
+            Resp::Array(Some(values)) | Resp::Set(values) | Resp::Push(values) => {
+                values.iter().map(Resp::node_count).sum()
+            }
+            Resp::Map(entries) => entries_node_count(entries),
+            Resp::Attribute { attributes, value } => {
+                entries_node_count(attributes) + value.node_count()
+            }
+            _ => 0,
+        };
+        1 + children
+    }
+
+    /// Returns name of the variant, e.g. for logging or error messages.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"$2\r\nOK\r\n"[..]).unwrap();
+    /// assert_eq!(parsed.type_name(), "BulkString");
+    /// ```
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Resp::SimpleString(_) => "SimpleString",
+            Resp::Integer(_) => "Integer",
+            Resp::Error(_) => "Error",
+            Resp::BulkString(_) => "BulkString",
+            Resp::Array(_) => "Array",
+            Resp::Double(_) => "Double",
+            Resp::Boolean(_) => "Boolean",
+            Resp::Null => "Null",
+            Resp::Map(_) => "Map",
+            Resp::Set(_) => "Set",
+            Resp::Push(_) => "Push",
+            Resp::BigNumber(_) => "BigNumber",
+            Resp::VerbatimString { .. } => "VerbatimString",
+            Resp::BulkError(_) => "BulkError",
+            Resp::Attribute { .. } => "Attribute",
+        }
+    }
+
+    /// Iterates over elements of `Array`, `Set` or `Push`, yields nothing for other variants.
+    ///
+    /// # Examples
+    /// ```
+    /// let (_, parsed) = respirator::resp(&b"*2\r\n:1\r\n:2\r\n"[..]).unwrap();
+    /// let sum: i64 = parsed.iter().filter_map(|value| value.as_integer()).sum();
+    /// assert_eq!(sum, 3);
+    /// ```
+    pub fn iter(&self) -> slice::Iter<'_, Resp> {
+        match self {
+            Resp::Array(Some(values)) | Resp::Set(values) | Resp::Push(values) => values.iter(),
+            _ => [].iter(),
+        }
+    }
+}
+
+/// Compares variants structurally, except that all `NaN` Doubles are equal to each other, which keeps equality reflexive.
+impl PartialEq for Resp {
+    fn eq(&self, other: &Resp) -> bool {
+        match (self, other) {
+            (Resp::SimpleString(a), Resp::SimpleString(b))
+            | (Resp::Error(a), Resp::Error(b))
+            | (Resp::BigNumber(a), Resp::BigNumber(b))
+            | (Resp::BulkError(a), Resp::BulkError(b)) => a == b,
+            (Resp::Integer(a), Resp::Integer(b)) => a == b,
+            (Resp::BulkString(a), Resp::BulkString(b)) => a == b,
+            (Resp::Array(a), Resp::Array(b)) => a == b,
+            (Resp::Double(a), Resp::Double(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Resp::Boolean(a), Resp::Boolean(b)) => a == b,
+            (Resp::Null, Resp::Null) => true,
+            (Resp::Map(a), Resp::Map(b)) => a == b,
+            (Resp::Set(a), Resp::Set(b)) | (Resp::Push(a), Resp::Push(b)) => a == b,
+            (
+                Resp::VerbatimString { format, data },
+                Resp::VerbatimString {
+                    format: other_format,
+                    data: other_data,
+                },
+            ) => format == other_format && data == other_data,
+            (
+                Resp::Attribute { attributes, value },
+                Resp::Attribute {
+                    attributes: other_attributes,
+                    value: other_value,
+                },
+            ) => attributes == other_attributes && value == other_value,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Resp {}
+
+/// Hashes variants structurally, consistently with `PartialEq`: `0.0` and `-0.0` Doubles hash the same,
+/// as do all `NaN` Doubles, as they are equal.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashSet;
+/// use respirator::Resp;
+///
+/// let replies: HashSet<Resp> = [Resp::from("a"), Resp::Integer(1), Resp::from("a")].into_iter().collect();
+/// assert_eq!(replies.len(), 2);
+/// ```
+impl Hash for Resp {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            Resp::SimpleString(val)
+            | Resp::Error(val)
+            | Resp::BigNumber(val)
+            | Resp::BulkError(val) => val.hash(state),
+            Resp::Integer(val) => val.hash(state),
+            Resp::BulkString(val) => val.hash(state),
+            Resp::Array(values) => values.hash(state),
+            Resp::Double(val) => {
+                let val = if *val == 0.0 {
+                    0.0
+                } else if val.is_nan() {
+                    f64::NAN
+                } else {
+                    *val
+                };
+                val.to_bits().hash(state)
+            }
+            Resp::Boolean(val) => val.hash(state),
+            Resp::Null => {}
+            Resp::Map(entries) => entries.hash(state),
+            Resp::Set(values) | Resp::Push(values) => values.hash(state),
+            Resp::VerbatimString { format, data } => {
+                format.hash(state);
+                data.hash(state);
+            }
+            Resp::Attribute { attributes, value } => {
+                attributes.hash(state);
+                value.hash(state);
+            }
+        }
+    }
+}
+
+/// Compares payload of `SimpleString`, non-null `BulkString`, `Error` or `BulkError` with the string,
+/// values of other variants are never equal to it.
+///
+/// # Examples
+/// ```
+/// let (_, parsed) = respirator::resp(&b"+OK\r\n"[..]).unwrap();
+/// assert!(parsed == "OK");
+/// assert!(respirator::Resp::Integer(1) != "1");
+/// ```
+impl PartialEq<&str> for Resp {
+    fn eq(&self, other: &&str) -> bool {
+        text(self) == Some(other.as_bytes())
+    }
+}
+
+/// Compares payload of `SimpleString`, non-null `BulkString`, `Error` or `BulkError` with the bytes,
+/// values of other variants are never equal to them.
+///
+/// # Examples
+/// ```
+/// let (_, parsed) = respirator::resp(&b"$3\r\n\x00\x01\x02\r\n"[..]).unwrap();
+/// assert!(parsed == &b"\x00\x01\x02"[..]);
+/// ```
+impl PartialEq<&[u8]> for Resp {
+    fn eq(&self, other: &&[u8]) -> bool {
+        text(self) == Some(*other)
+    }
+}
+
+/// Payload of string-like variants, which may be compared with text.
+fn text(value: &Resp) -> Option<&[u8]> {
+    match value {
+        Resp::SimpleString(val)
+        | Resp::BulkString(Some(val))
+        | Resp::Error(val)
+        | Resp::BulkError(val) => Some(val),
+        _ => None,
+    }
+}
+
+/// Checks whether value is a string-like map key with given payload.
+fn is_key(value: &Resp, key: &[u8]) -> bool {
+    matches!(value, Resp::SimpleString(val) | Resp::BulkString(Some(val)) if val == key)
+}
+
+fn split_error(val: &[u8]) -> (&[u8], &[u8]) {
+    match val.iter().position(|byte| *byte == b' ') {
+        Some(space) => (&val[..space], &val[space + 1..]),
+        None => (val, &[]),
+    }
+}
+
+fn entries_node_count(entries: &[(Resp, Resp)]) -> usize {
+    entries
+        .iter()
+        .map(|(key, value)| key.node_count() + value.node_count())
+        .sum()
+}
+
+/// Consumes `Array`, `Set` or `Push` yielding its owned elements, yields nothing for other variants.
+///
+/// # Examples
+/// ```
+/// use respirator::Resp;
+///
+/// let array = Resp::Array(Some(vec![Resp::Integer(1), Resp::Null]));
+/// assert_eq!(array.into_iter().collect::<Vec<_>>(), vec![Resp::Integer(1), Resp::Null]);
+/// ```
+impl IntoIterator for Resp {
+    type Item = Resp;
+    type IntoIter = vec::IntoIter<Resp>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Resp::Array(Some(values)) | Resp::Set(values) | Resp::Push(values) => {
+                values.into_iter()
+            }
+            _ => Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Resp {
+    type Item = &'a Resp;
+    type IntoIter = slice::Iter<'a, Resp>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Resp;
+
+    #[test]
+    fn accesses_matching_variants() {
+        assert_eq!(Resp::Integer(8).as_integer(), Some(8));
+        assert_eq!(
+            Resp::BulkString(Some(b"str".to_vec())).as_bulk_string(),
+            Some(&b"str"[..])
+        );
+        assert_eq!(
+            Resp::SimpleString(b"OK".to_vec()).as_simple_string(),
+            Some(&b"OK"[..])
+        );
+        assert_eq!(
+            Resp::Array(Some(vec![Resp::Null])).as_array(),
+            Some(&[Resp::Null][..])
+        );
+        assert_eq!(
+            Resp::BulkError(b"ERR".to_vec()).as_error(),
+            Some(&b"ERR"[..])
+        );
+    }
+
+    #[test]
+    fn takes_elements() {
+        let values = vec![Resp::Integer(1), Resp::Null];
+        assert_eq!(
+            Resp::Array(Some(values.clone())).take_array(),
+            Some(values.clone())
+        );
+        assert_eq!(Resp::Push(values.clone()).take_array(), Some(values));
+        assert_eq!(Resp::Array(None).take_array(), None);
+        assert_eq!(Resp::Map(vec![]).take_array(), None);
+        assert_eq!(Resp::Integer(1).take_array(), None);
+    }
+
+    #[test]
+    fn flattens_nested_aggregates() {
+        let nested = Resp::Push(vec![
+            Resp::Array(Some(vec![])),
+            Resp::Set(vec![Resp::Array(Some(vec![Resp::from("a")])), Resp::Null]),
+            Resp::Map(vec![(Resp::from("k"), Resp::Integer(1))]),
+            Resp::BulkString(None),
+        ]);
+        assert_eq!(
+            nested.flatten(),
+            vec![
+                Resp::from("a"),
+                Resp::Null,
+                Resp::Map(vec![(Resp::from("k"), Resp::Integer(1))]),
+                Resp::BulkString(None),
+            ]
+        );
+        assert_eq!(Resp::Array(None).flatten(), Vec::<Resp>::new());
+        assert_eq!(Resp::Integer(8).flatten(), vec![Resp::Integer(8)]);
+    }
+
+    #[test]
+    fn counts_nodes() {
+        assert_eq!(Resp::Integer(8).node_count(), 1);
+        assert_eq!(Resp::Array(None).node_count(), 1);
+        assert_eq!(Resp::Array(Some(vec![])).node_count(), 1);
+        let nested = Resp::Attribute {
+            attributes: vec![(Resp::from("ttl"), Resp::Integer(1))],
+            value: Box::new(Resp::Push(vec![Resp::Set(vec![Resp::Null, Resp::Null])])),
+        };
+        assert_eq!(nested.node_count(), 7);
+    }
+
+    #[test]
+    fn accesses_text_payloads() {
+        assert_eq!(Resp::SimpleString(b"OK".to_vec()).as_str(), Some("OK"));
+        assert_eq!(
+            Resp::BulkString(Some(b"str".to_vec())).as_str(),
+            Some("str")
+        );
+        assert_eq!(Resp::Error(b"ERR".to_vec()).as_str(), Some("ERR"));
+        assert_eq!(Resp::BulkString(Some(b"\xff".to_vec())).as_str(), None);
+        assert_eq!(Resp::BulkString(None).as_str(), None);
+        assert_eq!(Resp::Integer(8).as_str(), None);
+    }
+
+    #[test]
+    fn compares_with_strings() {
+        assert!(Resp::SimpleString(b"OK".to_vec()) == "OK");
+        assert!(Resp::BulkString(Some(b"OK".to_vec())) == &b"OK"[..]);
+        assert!(Resp::Error(b"ERR".to_vec()) == "ERR");
+        assert!(Resp::SimpleString(b"OK".to_vec()) != "ok");
+        assert!(Resp::BulkString(None) != "");
+        assert!(Resp::Integer(1) != "1");
+        assert!(Resp::Array(Some(vec![])) != &b""[..]);
+    }
+
+    #[test]
+    fn measures_string_payloads() {
+        assert_eq!(Resp::SimpleString(b"OK".to_vec()).byte_len(), Some(2));
+        assert_eq!(Resp::BulkString(Some(vec![])).byte_len(), Some(0));
+        assert_eq!(Resp::BulkError(b"ERR".to_vec()).byte_len(), Some(3));
+        assert_eq!(Resp::BulkString(None).byte_len(), None);
+        assert_eq!(Resp::Integer(100).byte_len(), None);
+    }
+
+    #[test]
+    fn coerces_floats() {
+        assert_eq!(Resp::Double(2.5).as_f64(), Some(2.5));
+        assert_eq!(
+            Resp::BulkString(Some(b"-1.5e3".to_vec())).as_f64(),
+            Some(-1500.0)
+        );
+        assert_eq!(
+            Resp::SimpleString(b"inf".to_vec()).as_f64(),
+            Some(f64::INFINITY)
+        );
+        assert!(Resp::BulkString(Some(b"nan".to_vec()))
+            .as_f64()
+            .unwrap()
+            .is_nan());
+        assert_eq!(Resp::BulkString(Some(b"1.2.3".to_vec())).as_f64(), None);
+        assert_eq!(Resp::Integer(1).as_f64(), None);
+    }
+
+    #[test]
+    fn looks_up_map_entries() {
+        let map = Resp::Map(vec![
+            (Resp::Integer(1), Resp::Integer(10)),
+            ("proto".into(), Resp::Integer(2)),
+            (Resp::SimpleString(b"proto".to_vec()), Resp::Integer(3)),
+        ]);
+        assert_eq!(map.map_get(b"proto"), Some(&Resp::Integer(2)));
+        assert_eq!(map.map_get(b"1"), None);
+        assert_eq!(map.hello_proto(), Some(2));
+        assert_eq!(Resp::Integer(3).hello_proto(), None);
+
+        let flat = Resp::Array(Some(vec![
+            "a".into(),
+            Resp::Integer(1),
+            Resp::Integer(2),
+            "a".into(),
+            "a".into(),
+            Resp::Integer(3),
+        ]));
+        assert_eq!(flat.map_get(b"a"), Some(&Resp::Integer(1)));
+        assert_eq!(flat.map_get(b"2"), None);
+        let odd = Resp::Array(Some(vec!["a".into(), Resp::Integer(1), "b".into()]));
+        assert_eq!(odd.map_get(b"a"), None);
+        assert_eq!(Resp::Set(vec!["a".into(), "b".into()]).map_get(b"a"), None);
+    }
+
+    #[test]
+    fn splits_error_code() {
+        let error = Resp::error(
+            "WRONGTYPE",
+            "Operation against a key holding the wrong kind of value",
+        );
+        assert_eq!(error.error_code(), Some(&b"WRONGTYPE"[..]));
+        assert_eq!(
+            error.error_message(),
+            Some(&b"Operation against a key holding the wrong kind of value"[..])
+        );
+
+        let bare = Resp::BulkError(b"ERR".to_vec());
+        assert_eq!(bare.error_code(), Some(&b"ERR"[..]));
+        assert_eq!(bare.error_message(), Some(&b""[..]));
+
+        assert_eq!(Resp::SimpleString(b"OK".to_vec()).error_code(), None);
+        assert_eq!(Resp::SimpleString(b"OK".to_vec()).error_message(), None);
+    }
+
+    #[test]
+    fn normalizes_error_parts() {
+        assert_eq!(
+            Resp::BulkError(b"WRONGTYPE Operation".to_vec()).error_parts(),
+            Some((Some(&b"WRONGTYPE"[..]), &b"Operation"[..]))
+        );
+        assert_eq!(
+            Resp::Error(b"NOAUTH".to_vec()).error_parts(),
+            Some((Some(&b"NOAUTH"[..]), &b""[..]))
+        );
+        for val in [&b"Err x"[..], b"ERR1 x", b" ERR x", b""] {
+            assert_eq!(Resp::Error(val.to_vec()).error_parts(), Some((None, val)));
+        }
+        assert_eq!(Resp::SimpleString(b"ERR x".to_vec()).error_parts(), None);
+    }
+
+    #[test]
+    fn inspects_verbatim_format() {
+        let markdown = Resp::VerbatimString {
+            format: *b"mkd",
+            data: b"# Title".to_vec(),
+        };
+        assert_eq!(markdown.verbatim_format(), Some("mkd"));
+        assert!(markdown.verbatim_is_markdown());
+
+        let invalid = Resp::VerbatimString {
+            format: *b"\xffxt",
+            data: vec![],
+        };
+        assert_eq!(invalid.verbatim_format(), None);
+        assert!(!invalid.verbatim_is_markdown());
+
+        assert_eq!(
+            Resp::BulkString(Some(b"mkd".to_vec())).verbatim_format(),
+            None
+        );
+        assert!(!Resp::BulkString(Some(b"mkd".to_vec())).verbatim_is_markdown());
+    }
+
+    #[test]
+    fn hashes_equal_values_equally() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash(value: &Resp) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        assert_eq!(hash(&Resp::Double(0.0)), hash(&Resp::Double(-0.0)));
+        assert_eq!(
+            hash(&Resp::Array(Some(vec![Resp::Integer(1)]))),
+            hash(&Resp::Array(Some(vec![Resp::Integer(1)])))
+        );
+        assert_ne!(
+            hash(&Resp::SimpleString(b"a".to_vec())),
+            hash(&Resp::Error(b"a".to_vec()))
+        );
+        assert_ne!(hash(&Resp::BulkString(None)), hash(&Resp::Array(None)));
+        assert_eq!(
+            hash(&Resp::Double(f64::NAN)),
+            hash(&Resp::Double(-f64::NAN))
+        );
+    }
+
+    #[test]
+    fn finds_nan_doubles_in_hashed_collections() {
+        use std::collections::HashSet;
+
+        let nan = Resp::Double(f64::NAN);
+        assert_eq!(nan, nan.clone());
+        assert_eq!(nan, Resp::Double(-f64::NAN));
+        assert_ne!(nan, Resp::Double(0.0));
+        let replies: HashSet<Resp> = [Resp::Array(Some(vec![nan.clone()]))].into_iter().collect();
+        assert!(replies.contains(&Resp::Array(Some(vec![nan]))));
+    }
+
+    #[test]
+    fn names_variants() {
+        assert_eq!(Resp::Integer(8).type_name(), "Integer");
+        assert_eq!(Resp::BulkString(None).type_name(), "BulkString");
+        assert_eq!(Resp::Array(None).type_name(), "Array");
+        assert_eq!(Resp::Map(vec![]).type_name(), "Map");
+        assert_eq!(
+            Resp::VerbatimString {
+                format: *b"txt",
+                data: vec![]
+            }
+            .type_name(),
+            "VerbatimString"
+        );
+    }
+
+    #[test]
+    fn returns_none_on_type_mismatch() {
+        assert_eq!(Resp::SimpleString(b"8".to_vec()).as_integer(), None);
+        assert_eq!(Resp::BulkString(None).as_bulk_string(), None);
+        assert_eq!(
+            Resp::BulkString(Some(b"OK".to_vec())).as_simple_string(),
+            None
+        );
+        assert_eq!(Resp::Array(None).as_array(), None);
+        assert_eq!(Resp::Set(vec![]).as_array(), None);
+        assert_eq!(Resp::SimpleString(b"ERR".to_vec()).as_error(), None);
+    }
+
+    #[test]
+    fn checks_predicates() {
+        assert!(Resp::Error(b"ERR".to_vec()).is_error());
+        assert!(Resp::BulkError(b"ERR".to_vec()).is_error());
+        assert!(!Resp::SimpleString(b"ERR".to_vec()).is_error());
+
+        assert!(Resp::SimpleString(b"OK".to_vec()).is_ok());
+        assert!(Resp::SimpleString(b"QUEUED".to_vec()).is_queued());
+        assert!(Resp::SimpleString(b"PONG".to_vec()).is_pong());
+        assert!(!Resp::BulkString(Some(b"OK".to_vec())).is_ok());
+        assert!(!Resp::SimpleString(b"ok".to_vec()).is_ok());
+        assert!(!Resp::SimpleString(b"PONG".to_vec()).is_queued());
+
+        assert!(Resp::BulkString(None).is_null());
+        assert!(Resp::Array(None).is_null());
+        assert!(Resp::Null.is_null());
+        assert!(!Resp::BulkString(Some(vec![])).is_null());
+        assert!(!Resp::Array(Some(vec![])).is_null());
+
+        assert!(Resp::Array(Some(vec![])).is_aggregate());
+        assert!(Resp::Map(vec![]).is_aggregate());
+        assert!(Resp::Set(vec![]).is_aggregate());
+        assert!(Resp::Push(vec![]).is_aggregate());
+        assert!(!Resp::Array(None).is_aggregate());
+        assert!(!Resp::Integer(1).is_aggregate());
+    }
+
+    #[test]
+    fn iterates_over_elements() {
+        let array = Resp::Array(Some(vec![Resp::Integer(1), Resp::Integer(2)]));
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![&Resp::Integer(1), &Resp::Integer(2)]
+        );
+        assert_eq!((&array).into_iter().count(), 2);
+        assert_eq!(Resp::Set(vec![Resp::Null]).iter().count(), 1);
+        assert_eq!(Resp::Push(vec![Resp::Null]).iter().count(), 1);
+        assert_eq!(Resp::Array(None).iter().count(), 0);
+        assert_eq!(Resp::Integer(1).iter().count(), 0);
+    }
+
+    #[test]
+    fn consumes_elements() {
+        let push = Resp::Push(vec![Resp::from("message"), Resp::from("hello")]);
+        assert_eq!(
+            push.into_iter().collect::<Vec<_>>(),
+            vec![Resp::from("message"), Resp::from("hello")]
+        );
+        assert_eq!(Resp::Null.into_iter().count(), 0);
+    }
+}
@@ -0,0 +1,295 @@
+use nom::{Err, IResult};
+
+use crate::{
+    parser::{length, nullable_length, take_bytes, value, within_limit, Context},
+    ParseConfig, RespError, RespRef,
+};
+
+/// Callbacks invoked by [`parse_events`] for every value, in depth-first order.
+/// Aggregates are reported as a start event with their length, events of their elements and an end event.
+/// All callbacks do nothing by default, so implementors only override the ones they care about.
+#[allow(unused_variables)]
+pub trait RespVisitor {
+    /// Called for Simple String.
+    fn on_simple_string(&mut self, val: &[u8]) {}
+    /// Called for Integer.
+    fn on_integer(&mut self, val: i64) {}
+    /// Called for Error.
+    fn on_error(&mut self, val: &[u8]) {}
+    /// Called for Bulk String, with None for null bulk string (`$-1`).
+    fn on_bulk_string(&mut self, val: Option<&[u8]>) {}
+    /// Called for streamed Bulk String in RESP3 (`$?`), with its chunks in order.
+    fn on_streamed_string(&mut self, chunks: &[&[u8]]) {}
+    /// Called before elements of an Array.
+    fn on_array_start(&mut self, len: usize) {}
+    /// Called after all elements of an Array.
+    fn on_array_end(&mut self) {}
+    /// Called for null array (`*-1`), which has no start nor end event.
+    fn on_null_array(&mut self) {}
+    /// Called for Double.
+    fn on_double(&mut self, val: f64) {}
+    /// Called for Boolean.
+    fn on_boolean(&mut self, val: bool) {}
+    /// Called for Null.
+    fn on_null(&mut self) {}
+    /// Called before entries of a Map, `len` is the number of entries, each reported as key followed by value.
+    fn on_map_start(&mut self, len: usize) {}
+    /// Called after all entries of a Map.
+    fn on_map_end(&mut self) {}
+    /// Called before elements of a Set.
+    fn on_set_start(&mut self, len: usize) {}
+    /// Called after all elements of a Set.
+    fn on_set_end(&mut self) {}
+    /// Called before elements of a Push.
+    fn on_push_start(&mut self, len: usize) {}
+    /// Called after all elements of a Push.
+    fn on_push_end(&mut self) {}
+    /// Called for Big Number.
+    fn on_big_number(&mut self, val: &[u8]) {}
+    /// Called for Verbatim String.
+    fn on_verbatim_string(&mut self, format: [u8; 3], data: &[u8]) {}
+    /// Called for Bulk Error.
+    fn on_bulk_error(&mut self, val: &[u8]) {}
+    /// Called before entries of an Attribute, reported like entries of a Map.
+    fn on_attribute_start(&mut self, len: usize) {}
+    /// Called after all entries of an Attribute, events of the value it is attached to follow.
+    fn on_attribute_end(&mut self) {}
+}
+
+/// Parses single RESP value, reporting it to the visitor instead of building a [`Resp`](crate::Resp) tree.
+/// Payloads passed to callbacks borrow from the input.
+/// On malformed input, events of the values parsed before the error have already been reported.
+/// Streamed aggregates (e.g. `*?`) are not supported, as their length can't be reported up front.
+///
+/// # Arguments
+///
+/// * `input` - a byte slice to be parsed
+/// * `visitor` - callbacks to report parsed values to
+///
+/// # Examples
+/// ```
+/// use respirator::{parse_events, RespVisitor};
+///
+/// #[derive(Default)]
+/// struct Lengths(Vec<usize>);
+///
+/// impl RespVisitor for Lengths {
+///     fn on_bulk_string(&mut self, val: Option<&[u8]>) {
+///         self.0.push(val.map_or(0, <[u8]>::len));
+///     }
+/// }
+///
+/// let mut lengths = Lengths::default();
+/// parse_events(&b"*2\r\n$3\r\nfoo\r\n$6\r\nfoobar\r\n"[..], &mut lengths).unwrap();
+/// assert_eq!(lengths.0, vec![3, 6]);
+/// ```
+pub fn parse_events<'a>(
+    input: &'a [u8],
+    visitor: &mut dyn RespVisitor,
+) -> IResult<&'a [u8], (), RespError> {
+    visit(input, visitor, Context::new(false, ParseConfig::default()))
+}
+
+fn visit<'a>(
+    input: &'a [u8],
+    visitor: &mut dyn RespVisitor,
+    ctx: Context,
+) -> IResult<&'a [u8], (), RespError> {
+    let (rest, val) = take_bytes(input, 1, ctx)?;
+    match val[0] {
+        b'*' => {
+            let (rest, len) = match nullable_length(rest, ctx)? {
+                (rest, Some(len)) => (rest, len),
+                (rest, None) => {
+                    visitor.on_null_array();
+                    return Ok((rest, ()));
+                }
+            };
+            within_limit(len, ctx.limits.max_array_len)?;
+            visitor.on_array_start(len);
+            let rest = elements(rest, visitor, ctx.nested()?, len)?;
+            visitor.on_array_end();
+            Ok((rest, ()))
+        }
+        b'%' => {
+            let ctx = ctx.nested()?;
+            let (rest, len) = length(rest, ctx)?;
+            within_limit(len, ctx.limits.max_map_len)?;
+            visitor.on_map_start(len);
+            let mut rest = rest;
+            for _ in 0..len {
+                rest = elements(rest, visitor, ctx, 2)?;
+            }
+            visitor.on_map_end();
+            Ok((rest, ()))
+        }
+        b'~' => {
+            let ctx = ctx.nested()?;
+            let (rest, len) = length(rest, ctx)?;
+            within_limit(len, ctx.limits.max_array_len)?;
+            visitor.on_set_start(len);
+            let rest = elements(rest, visitor, ctx, len)?;
+            visitor.on_set_end();
+            Ok((rest, ()))
+        }
+        b'>' => {
+            let ctx = ctx.nested()?;
+            let (rest, len) = length(rest, ctx)?;
+            within_limit(len, ctx.limits.max_array_len)?;
+            visitor.on_push_start(len);
+            let rest = elements(rest, visitor, ctx, len)?;
+            visitor.on_push_end();
+            Ok((rest, ()))
+        }
+        b'|' => {
+            let ctx = ctx.nested()?;
+            let (rest, len) = length(rest, ctx)?;
+            within_limit(len, ctx.limits.max_map_len)?;
+            visitor.on_attribute_start(len);
+            let mut rest = rest;
+            for _ in 0..len {
+                rest = elements(rest, visitor, ctx, 2)?;
+            }
+            visitor.on_attribute_end();
+            visit(rest, visitor, ctx)
+        }
+        _ => {
+            let (rest, val) = value(input, ctx)?;
+            scalar(val, visitor);
+            Ok((rest, ()))
+        }
+    }
+}
+
+fn elements<'a>(
+    mut input: &'a [u8],
+    visitor: &mut dyn RespVisitor,
+    ctx: Context,
+    len: usize,
+) -> Result<&'a [u8], Err<RespError>> {
+    for _ in 0..len {
+        input = visit(input, visitor, ctx)?.0;
+    }
+    Ok(input)
+}
+
+fn scalar(val: RespRef<'_>, visitor: &mut dyn RespVisitor) {
+    match val {
+        RespRef::SimpleString(val) => visitor.on_simple_string(val),
+        RespRef::Integer(val) => visitor.on_integer(val),
+        RespRef::Error(val) => visitor.on_error(val),
+        RespRef::BulkString(val) => visitor.on_bulk_string(val),
+        RespRef::StreamedString(chunks) => visitor.on_streamed_string(&chunks),
+        RespRef::Double(val) => visitor.on_double(val),
+        RespRef::Boolean(val) => visitor.on_boolean(val),
+        RespRef::Null => visitor.on_null(),
+        RespRef::BigNumber(val) => visitor.on_big_number(val),
+        RespRef::VerbatimString { format, data } => visitor.on_verbatim_string(format, data),
+        RespRef::BulkError(val) => visitor.on_bulk_error(val),
+        RespRef::Array(_)
+        | RespRef::Map(_)
+        | RespRef::Set(_)
+        | RespRef::Push(_)
+        | RespRef::Attribute { .. } => {
+            unreachable!("aggregates are visited element by element")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records every event as a short string.
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl RespVisitor for Recorder {
+        fn on_simple_string(&mut self, val: &[u8]) {
+            self.0.push(format!("+{}", String::from_utf8_lossy(val)));
+        }
+
+        fn on_integer(&mut self, val: i64) {
+            self.0.push(format!(":{}", val));
+        }
+
+        fn on_bulk_string(&mut self, val: Option<&[u8]>) {
+            match val {
+                Some(val) => self.0.push(format!("${}", String::from_utf8_lossy(val))),
+                None => self.0.push("$nil".to_string()),
+            }
+        }
+
+        fn on_array_start(&mut self, len: usize) {
+            self.0.push(format!("[{}", len));
+        }
+
+        fn on_array_end(&mut self) {
+            self.0.push("]".to_string());
+        }
+
+        fn on_null_array(&mut self) {
+            self.0.push("*nil".to_string());
+        }
+
+        fn on_map_start(&mut self, len: usize) {
+            self.0.push(format!("{{{}", len));
+        }
+
+        fn on_map_end(&mut self) {
+            self.0.push("}".to_string());
+        }
+
+        fn on_push_start(&mut self, len: usize) {
+            self.0.push(format!(">{}", len));
+        }
+
+        fn on_push_end(&mut self) {
+            self.0.push("<".to_string());
+        }
+    }
+
+    #[test]
+    fn visits_nested_aggregates() {
+        let input = &b"*3\r\n%1\r\n+a\r\n*1\r\n:1\r\n$-1\r\n>1\r\n*-1\r\n+rest"[..];
+        let mut recorder = Recorder::default();
+        let (rest, ()) = parse_events(input, &mut recorder).unwrap();
+        assert_eq!(rest, b"+rest");
+        assert_eq!(
+            recorder.0,
+            vec!["[3", "{1", "+a", "[1", ":1", "]", "}", "$nil", ">1", "*nil", "<", "]"]
+        );
+    }
+
+    #[test]
+    fn ignores_events_without_callbacks() {
+        struct Nothing;
+        impl RespVisitor for Nothing {}
+
+        let input = &b"~2\r\n#t\r\n,2.5\r\n"[..];
+        let (rest, ()) = parse_events(input, &mut Nothing).unwrap();
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn reports_events_before_error() {
+        let mut recorder = Recorder::default();
+        let parsed = parse_events(&b"*2\r\n:1\r\n?\r\n"[..], &mut recorder);
+        assert_eq!(parsed, Err(Err::Error(RespError::UnknownType(b'?'))));
+        assert_eq!(recorder.0, vec!["[2", ":1"]);
+    }
+
+    #[test]
+    fn rejects_negative_array_lengths() {
+        let parsed = parse_events(&b"*-2\r\n"[..], &mut Recorder::default());
+        assert_eq!(parsed, Err(Err::Error(RespError::InvalidLength)));
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        let mut input = b"*1\r\n".repeat(100_000);
+        input.extend_from_slice(b":1\r\n");
+        let parsed = parse_events(&input, &mut Recorder::default());
+        assert_eq!(parsed, Err(Err::Error(RespError::DepthExceeded)));
+    }
+}